@@ -0,0 +1,137 @@
+# The Catalan translation of the story, which is written in Spanish in `assets/story/book.story.ron`.
+# Keys are the node, then the choice if any, then the field. Texts with cases
+# have a key per case, in the order they are written in the story.
+# The variables of the story can be used, like `{ $princesa }`.
+
+node-0-text = Hi havia una vegada un *terrible drac* que atemoria la vila de Montblanc...
+node-0-choice-0-text = Hi havia una vegada *un home clarament disfressat de drac* que, per algun motiu, atemoria la vila de Montblanc...
+node-0-choice-0-additional-text = Potser era que es veien pocs dracs o que de veritat tenien ganes de poder dir que n'havien vist un, però fos com fos, tota la vila semblava convençuda que ho era.
+node-0-choice-1-text = Hi havia una vegada *un drac normalet*, amb els seus problemes i les seves coses, la presència del qual atemoria la vila de Montblanc...
+node-0-choice-1-additional-text = De fet no els feia res, però un drac gegant que escopia foc era l'objectiu perfecte sobre el qual projectar els seus problemes.
+node-1-text = Encara immersos en els seus deliris, els habitants de la vila van començar a enviar-li bestiar i animals, esperant que això li sadollés la "ira". Tanmateix, *no va tenir gaire efecte*...
+node-1-additional-text = De fet, algunes vaques eren gairebé tan grosses com el "drac"...
+node-2-text = Amb la vila desesperada, el rei no va tenir més remei que fer un *sorteig* per oferir a la bèstia sacrificis humans, ignorant que el destí, confús i sibil·lí, es conjuraria en contra seu amb el sacrifici de la seva pròpia filla...
+node-2-choice-0-text = La princesa { $princesa }, cansada dels inútils intents de la gent de la vila per calmar la situació, es va oferir per *matar el drac*
+node-2-choice-0-additional-text = Espasa en mà i pas decidit, es va dirigir a la cova on s'amagava el drac.
+node-2-choice-1-text = La princesa { $princesa }, *delerosa de conèixer un drac de veritat*, es va oferir voluntària per resoldre la situació
+node-2-choice-1-additional-text = Amb la seva enciclopèdia de dracs preferida sota el braç, es va dirigir a la cova de la bèstia sense cap temor.
+node-2-choice-2-text = Però quan va veure que ell mateix havia estat l'escollit en el sorteig, va dir a tothom que la princesa { $princesa }, la seva pròpia filla, era la *dissortada víctima de la fortuna...*
+node-2-choice-2-additional-text = No va ser una gran sorpresa per a la { $princesa }, però tot i així va acceptar el seu destí i es va encaminar cap al cau del drac.
+node-3-text = La { $princesa } va acceptar el seu destí amb valentia i després d'un curt viatge va arribar al cau del drac, on *es va trobar...*
+node-3-choice-0-text = Un home misteriós disfressat de drac torrant núvols de sucre.
+node-3-choice-0-additional-text = Tothom sap que no hi ha res que un drac gaudeixi més que seure com un humà a torrar llaminadures davant del foc...
+node-3-choice-1-text = Un cavaller ben plantat pintant-se la cara per a la guerra, encoratjant-se a si mateix...
+node-3-choice-1-additional-text = El seu llibre preferit era "L'art de la guerra" i especulava amb terrenys
+node-3-choice-2-text = Un cavaller ben plantat davant del cau de la temible bèstia subjectant ferotgement un... ram de flors?
+node-3-choice-2-additional-text = No tots els cavallers han de ser agressius, segur que Sant Jordi tindria els seus "mètodes"...
+node-4-text = Una mica decebuda davant l'innocent i força adorable dragonet que tenia al davant, la { $princesa } no es va acovardir i li va clavar a la bèstia un bon clatellot a la closca...
+node-4-additional-text = Era evident que la princesa no havia fet tot aquest viatge per quedar-se ara de braços plegats.
+node-5-text = Va ser tan contundent la garrotada que el cap del "drac" va sortir volant i va revelar l'home que s'havia estat fent passar per la bèstia tot aquest temps: Sant Jordi!
+node-5-additional-text = La princesa, irada, va exigir explicacions a Sant Jordi, indignada davant d'un deshonor semblant a la cavalleria.
+node-6-text = Sant Jordi va confessar que durant tot aquest temps s'havia estat disfressant de drac, aprofitant-se de la gent de Montblanc que, aterrida, no parava de fer-li regals i donar-li coses de franc.
+node-6-additional-text = Però abans que pogués acabar d'explicar-se i justificar-se, el drac (que pel que sembla havia estat de vacances) va tornar, i el pobre cavaller es va quedar glaçat.
+node-7-text = Al final, el drac i la princesa es van convertir en uns aliats improbables davant la idiotesa de Sant Jordi i del poble, de manera que van acordar una solució que, per fi, posaria punt final a la disputa...
+node-7-additional-text = I així, la vila de Montblanc va tornar a la normalitat i la tranquil·litat que la caracteritzaven... Almenys, fins que aparegués el següent "drac"...
+node-8-text = Fascinada per l'estrany exemplar que tenia al davant, va començar a examinar-lo exhaustivament
+node-8-additional-text = Mentre que a qualsevol altre habitant de la vila li tremolarien les mans de terror, a ella li tremolaven de pura emoció.
+node-9-text = Després del seu minuciós examen, per a la seva decepció, va veure clarament que tenia al davant una disfressa. Mai no hi havia hagut cap drac! La princesa, trista, va acusar la bèstia, que va resultar ser... Sant Jordi?
+node-9-additional-text = Entre el plor i la ira, la { $princesa } va exigir explicacions al vil cavaller.
+node-10-text = A diferència de la resta de la vila, la { $princesa } tenia una mica de seny i va reconèixer ràpidament que allò que tenia al davant no era un drac...
+node-10-additional-text = De fet, si t'hi fixaves bé, es veien clarament les costures del vestit de drac.
+node-11-text = La princesa, ja una mica cansada de tanta ximpleria, va arrencar la màscara al fals drac i va revelar que darrere de tota aquesta farsa hi havia... Sant Jordi!
+node-11-additional-text = Lluny d'enfadar-se o d'indignar-se, la { $princesa } es va veure inundada per una terrible onada de frustració.
+node-12-text = Sant Jordi, traient pit i picant-li l'ullet, li va dir a la { $princesa }: "tranquil·la princesa, jo m'encarrego del dragonet". I sense esperar resposta, el cavaller es va endinsar a les profunditats de la cova de la bèstia. Aleshores, la { $princesa } va decidir...
+node-12-choice-0-text = La { $princesa } va decidir entrar a la cova amb Sant Jordi.
+node-12-choice-0-additional-text-0 = La { $princesa }, encara amb ganes d'una mica d'acció, va seguir el cavaller procurant que no la veiés.
+node-12-choice-0-additional-text-1 = La { $princesa }, massa delerosa per la possibilitat de veure un drac de veritat, va seguir de prop Sant Jordi i es va endinsar darrere d'ell al cau de la criatura.
+node-12-choice-0-additional-text-2 = Sense acabar de creure's el cavaller, potser una mica fatxenda, la { $princesa } el va seguir de prop i es va endinsar al cau de la bèstia.
+node-12-choice-1-text = La { $princesa } va decidir esperar fora.
+node-12-choice-1-additional-text-0 = La { $princesa }, sabent que Sant Jordi no podria acabar amb un drac tot sol, es va quedar fora esperant que sortís esperitat a buscar ajuda.
+node-12-choice-1-additional-text-1 = Conscient del perill d'exposar-se a un drac de veritat, la { $princesa } va preferir esperar que el cavaller complís la seva comesa. Ja el podria examinar bé després.
+node-12-choice-1-additional-text-2 = La { $princesa } va esperar fora de la cova que Sant Jordi complís el seu deure, si és que era tan valent com deia...
+node-13-text = Per a sorpresa de la { $princesa } (tot i que tampoc gaire, per ser sincers) l'únic que va trobar dins la cova va ser Sant Jordi, tot sol, cridant i grunyint, lluitant contra la seva pròpia ombra...
+node-13-additional-text = Si bé el que estava fent Sant Jordi no quedava gaire clar, del que no hi havia cap dubte era que allà dins no hi havia cap drac.
+node-14-text = La { $princesa } es va adonar que alguna cosa no quadrava, sobretot quan en un racó del cau va veure el que semblava una disfressa de drac força barata llançada a terra... Sant Jordi havia estat el drac tot aquest temps!
+node-14-additional-text = Clarament el cavaller li havia estat prenent el pèl, però... per què?
+node-15-text = La princesa va acusar Sant Jordi, que va confessar que durant tot aquest temps s'havia estat disfressant de drac, per després caçar-se a si mateix i endur-se la fama. La { $princesa } va començar a renyar severament el cavaller que, sorprenentment, semblava aterrit...
+node-15-additional-text = Tot i que no precisament per ella...
+node-16-text = Després d'unes negociacions difícils, al final el drac i la princesa van acordar amb Sant Jordi una solució que resoldria la situació definitivament...
+node-16-additional-text = I així, la vila de Montblanc va tornar a la normalitat i la tranquil·litat que la caracteritzaven... Almenys, fins que aparegués el següent "drac"...
+node-17-text = Al cap de poc, Sant Jordi, esbufegant i brut, va sortir de la cova agafant... el cap del drac!
+node-17-additional-text = Així, a la llum del dia, tampoc semblava gran cosa, però bé, Sant Jordi se'l veia orgullós.
+node-18-text = La { $princesa }, una mica escèptica, es va adonar que alguna cosa no acabava d'encaixar...
+node-18-additional-text = De fet, es veien les costures del "cap del drac"... Què pretenia Sant Jordi amb tot això?
+node-19-text = Sant Jordi, visiblement incòmode, li va explicar a la { $princesa } que les roses havien brotat de la sang del drac quan el va matar...
+node-19-additional-text = La { $princesa }, una mica escèptica davant el més que evident nerviosisme del cavaller, li va demanar proves de la mort del drac.
+node-20-text = Sant Jordi, veient-se obligat a improvisar, es va ficar dins el cau del drac i, al cap d'una bona estona, en va tornar a sortir agafant... El cap del drac!
+node-20-additional-text-0 = La { $princesa }, una mica escèptica, es va adonar que alguna cosa no acabava d'encaixar...
+node-20-additional-text-1 = La { $princesa }, que de dracs en sabia un munt, va reconèixer que aquell cap clarament no era de veritat...
+node-21-text = Si encara quedava algun dubte que Sant Jordi no estava sent del tot sincer...
+node-21-additional-text = Diguem que de sobte tot es va aclarir...
+node-22-text = La princesa, escandalitzada, va decidir...
+node-22-choice-0-text = Anar a explicar l'idil·li a la resta de la vila.
+node-22-choice-0-additional-text = Xafardera i morbosa, la { $princesa } va córrer cap a la vila per compartir amb tothom l'aberrant i còmica relació contra natura que el drac i el cavaller mantenien.
+node-22-choice-1-text = Guardar el secret i explicar a la vila una llegenda inventada per encobrir-los.
+node-22-choice-1-additional-text = Commoguda per una mostra d'amor semblant en contra de tota mena de prejudicis, la princesa va decidir ajudar-los i mantenir el seu tòrrid idil·li en secret.
+node-23-text = La vila es va assabentar de l'idil·li prohibit del drac i Sant Jordi, cosa que va obligar la parella a viure la seva lluna de mel a Escòcia
+node-23-additional-text = Segur que allà serien més tolerants...
+node-24-text = La { $princesa } va explicar a la vila la gran gesta del cavaller Sant Jordi, que va vèncer el drac i de la sang del qual van brotar roses.
+node-24-additional-text = I així, un cop més, l'amor va prevaler per sobre de tot.
+node-25-text = Convençuts que el drac tenia intencions perverses, van intentar avançar-se a la desgràcia oferint-li molt de bestiar i animals, ignorant del tot que la bèstia era vegana...
+node-25-additional-text = Almenys el drac tindria companyia...
+node-26-text = Amb la vila desesperada, el rei no va tenir més remei que fer un sorteig per oferir a la bèstia sacrificis humans, ignorant que el destí, confús i sibil·lí, es conjuraria en contra seu amb el sacrifici de la seva pròpia filla...
+node-26-choice-0-text = La princesa { $princesa }, cansada dels inútils intents de la gent de la vila per calmar la situació, es va oferir voluntària per matar el drac.
+node-26-choice-0-additional-text = Espasa en mà i pas decidit, es va dirigir a la cova on s'amagava el drac.
+node-26-choice-1-text = La princesa { $princesa }, delerosa de conèixer un drac de veritat, es va oferir voluntària per fer servir els seus amplis coneixements de dracs per resoldre la situació.
+node-26-choice-1-additional-text = Amb la seva enciclopèdia de dracs preferida sota el braç, es va dirigir a la cova de la bèstia sense cap temor.
+node-26-choice-2-text = Però quan va veure que ell mateix havia estat l'escollit en el sorteig, presoner de la seva pròpia covardia, va dir a tothom que la princesa { $princesa }, la seva pròpia filla, era la dissortada víctima de la fortuna...
+node-26-choice-2-additional-text = No va ser una gran sorpresa per a la { $princesa }, però tot i així va acceptar el seu destí i es va encaminar cap al cau del drac.
+node-27-text = La { $princesa } va acceptar el seu destí amb valentia i després d'un curt viatge va arribar al cau del drac, on es va trobar...
+node-27-choice-0-text = Un home misteriós disfressat de drac torrant núvols de sucre.
+node-27-choice-0-additional-text = Tothom sap que no hi ha res que un drac gaudeixi més que seure com un humà a torrar llaminadures davant del foc...
+node-27-choice-1-text = Un cavaller ben plantat pintant-se la cara per a la guerra, encoratjant-se a si mateix...
+node-27-choice-1-additional-text = El seu llibre preferit era "L'art de la guerra" i especulava amb terrenys
+node-27-choice-2-text = Un cavaller ben plantat davant del cau de la temible bèstia subjectant ferotgement un... ram de flors?
+node-27-choice-2-additional-text = No tots els cavallers han de ser agressius, segur que Sant Jordi tindria els seus "mètodes"...
+node-28-text = La { $princesa }, intrigada per l'estrany drac, va decidir apropar-s'hi per "tantejar" el terreny, i el drac va respondre...
+node-28-choice-0-text = Deixant-se portar.
+node-28-choice-0-additional-text = L'amor funciona de maneres misterioses... Qui som nosaltres per jutjar?
+node-28-choice-1-text = Rebutjant, incòmode, l'estrany apropament de la { $princesa }.
+node-28-choice-1-additional-text = Sembla que no era el seu tipus... Ni la seva espècie...
+node-29-text = En veure que l'estrany dragonet responia al seu apropament, la { $princesa } va decidir que era el moment de revelar la seva veritable forma: ella era el drac!
+node-29-additional-text = Mare de Déu Senyor! Qui s'ho hauria imaginat! La filla del Rei... el drac?
+node-30-text = La { $princesa } i l'estrany drac, que clarament era un humà disfressat, van ser tots dos víctimes de les fletxes del caòtic Cupido i van iniciar un idil·li que desafiava tota lògica i raó.
+node-30-additional-text = I així, van viure feliços per sempre demostrant un cop més que l'amor és cec.
+node-31-text = La { $princesa } no es va acovardir i va continuar amb els seus apropaments, donant per fet que només es feia l'interessant. Tanmateix el drac, sentint-se assetjat pels continus i inexplicables avenços de la princesa, va revelar la seva veritable identitat. Era Sant Jordi tot aquest temps!
+node-31-additional-text = El reputat cavaller disfressat de drac... És que ja no quedava gent honrada?
+node-32-text = La { $princesa }, sentint-se enganyada i despitada, va revelar la seva veritable forma. Ella era el drac!
+node-32-additional-text = Sant Jordi no ho sabia però tot aquest temps havia estat jugant amb foc...
+node-33-text = La { $princesa }, com a escarment, va socarrimar una mica Sant Jordi, que va fugir esperitat. La princesa, després de treure's del mig el problemàtic fals drac que feia un flac favor als seus, va celebrar amb la resta de la vila la tornada a la normalitat.
+node-34-text = Sant Jordi, traient pit i picant-li l'ullet, li va dir a la { $princesa }: "tranquil·la princesa, jo m'encarrego del dragonet". I sense esperar resposta, el cavaller es va endinsar a les profunditats de la cova de la bèstia. Mentrestant, la { $princesa } va decidir...
+node-34-choice-0-text = entrar a la cova amb Sant Jordi.
+node-34-choice-0-additional-text-0 = La { $princesa }, encara amb ganes d'una mica d'acció, va seguir el cavaller procurant que no la veiés.
+node-34-choice-0-additional-text-1 = La { $princesa }, massa delerosa per la possibilitat de veure un drac de veritat, va seguir de prop Sant Jordi i es va endinsar darrere d'ell al cau de la criatura.
+node-34-choice-0-additional-text-2 = Sense acabar de creure's el cavaller, potser una mica fatxenda, la { $princesa } el va seguir de prop i es va endinsar al cau de la bèstia.
+node-34-choice-1-text = esperar fora.
+node-34-choice-1-additional-text-0 = La { $princesa }, sabent que Sant Jordi no podria acabar amb un drac tot sol, es va quedar fora esperant que sortís esperitat a buscar ajuda.
+node-34-choice-1-additional-text-1 = Conscient del perill d'exposar-se a un drac de veritat, la { $princesa } va preferir esperar que el cavaller complís la seva comesa. Ja el podria examinar bé després.
+node-34-choice-1-additional-text-2 = La { $princesa } va esperar fora de la cova que Sant Jordi complís el seu deure, si és que era tan valent com deia...
+node-35-text = Per a sorpresa de la { $princesa } (tot i que tampoc gaire, per ser sincers) l'únic que va trobar dins la cova va ser Sant Jordi, tot sol, cridant i grunyint, lluitant contra la seva pròpia ombra...
+node-35-additional-text = Si bé el que estava fent Sant Jordi no quedava gaire clar, del que no hi havia cap dubte era que allà dins no hi havia cap drac.
+node-36-text = Aleshores la { $princesa }, irritada, va revelar la seva veritable forma. Ella era el drac!
+node-36-additional-text = Sant Jordi no ho sabia però tot aquest temps havia estat jugant amb foc...
+node-37-text = La { $princesa }, com a escarment, va socarrimar una mica Sant Jordi, que va fugir esperitat del regne. La princesa va tornar a la vila i tothom va celebrar la seva victòria.
+node-38-text = Al cap de poc, Sant Jordi, esbufegant i brut, va sortir de la cova agafant... el cap del drac!
+node-38-additional-text = Així, a la llum del dia, tampoc semblava gran cosa, però bé, Sant Jordi se'l veia orgullós.
+node-39-text = Aleshores la { $princesa }, irritada, va revelar la seva veritable forma. Ella era el drac!
+node-39-additional-text = Sant Jordi no ho sabia però tot aquest temps havia estat jugant amb foc...
+node-40-text = La { $princesa }, com a escarment, va socarrimar una mica Sant Jordi, que va fugir esperitat del regne. La princesa va tornar a la vila i tothom va celebrar la seva victòria.
+node-41-text = La princesa, escandalitzada, va decidir...
+node-41-choice-0-text = Anar a explicar l'idil·li a la resta de la vila.
+node-41-choice-0-additional-text = Xafardera i morbosa, la { $princesa } va córrer cap a la vila per compartir amb tothom l'aberrant i còmica relació contra natura que el drac i el cavaller mantenien.
+node-41-choice-1-text = Guardar el secret.
+node-41-choice-1-additional-text = Commoguda per una mostra d'amor semblant en contra de tota mena de prejudicis, la princesa va decidir ajudar-los a mantenir el seu tòrrid idil·li en secret.
+node-42-text = La vila es va assabentar de l'idil·li prohibit del drac i Sant Jordi, cosa que va obligar la parella a viure la seva lluna de mel a Escòcia
+node-42-additional-text = Segur que allà serien més tolerants...
+node-43-text = La { $princesa } va explicar a la vila la gran gesta del cavaller Sant Jordi, que va vèncer el drac i de la sang del qual van brotar roses.
+node-43-additional-text = I així, un cop més, l'amor va prevaler per sobre de tot.
@@ -0,0 +1,76 @@
+# Texts of the menu and the book that aren't part of the story.
+
+menu-continue = Continuar
+# Each language is named in itself, so it can be found whatever the language.
+language-catalan = Català
+language-spanish = Castellano
+controls-mouse = Ratolí
+controls-space-bar = [Barra espaiadora]
+
+credits-pablo = Pablo Ferrer
+credits-alex = Álex Pérez
+credits-fran = Francisco Aguirre
+credits-claudia = Claudia Mohedano
+
+story-lost = La història s'ha *perdut* entre les pàgines del llibre...
+back-to-menu = Tornar al menú
+
+# The printed gamebook, like "Ves a l'apartat 12".
+gamebook-turn-to = Ves a l'apartat
+gamebook-the-end = Fi
+
+# The settings page of the menu.
+menu-settings = Ajustos
+settings-music-volume = Música
+settings-effects-volume = Efectes
+settings-mute = Silenci
+settings-text-pace = Velocitat del text
+settings-font-scale = Mida de la lletra
+settings-window = Pantalla
+settings-on = Sí
+settings-off = No
+settings-pace-slow = Lenta
+settings-pace-normal = Normal
+settings-pace-fast = Ràpida
+settings-pace-instant = Instantània
+settings-fullscreen = Completa
+settings-windowed = Finestra
+settings-back = Tornar
+settings-controls = Controls
+
+# The controls page, where the actions are rebound.
+controls-change = Canviar
+controls-press = Prem...
+controls-reset = Per defecte
+action-next-page = Passar pàgina
+action-previous-page = Tornar enrere
+action-focus-next = Opció següent
+action-focus-prev = Opció anterior
+action-confirm = Triar
+action-choose-1 = Triar la primera
+action-choose-2 = Triar la segona
+action-choose-3 = Triar la tercera
+action-menu = Menú
+action-skip = Acabar el text
+binding-arrow-up = Fletxa amunt
+binding-arrow-down = Fletxa avall
+binding-arrow-left = Fletxa esquerra
+binding-arrow-right = Fletxa dreta
+binding-space = Espai
+binding-enter = Retorn
+binding-backspace = Retrocés
+binding-page-up = RePàg
+binding-page-down = AvPàg
+binding-click = Clic
+binding-right-click = Clic dret
+binding-wheel-up = Roda amunt
+binding-wheel-down = Roda avall
+binding-tap = Toc
+binding-swipe-left = Lliscar a l'esquerra
+binding-swipe-right = Lliscar a la dreta
+binding-dpad-up = Creueta amunt
+binding-dpad-down = Creueta avall
+binding-dpad-left = Creueta esquerra
+binding-dpad-right = Creueta dreta
+binding-stick-forward = Palanca avall
+binding-stick-back = Palanca amunt
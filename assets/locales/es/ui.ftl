@@ -0,0 +1,76 @@
+# Texts of the menu and the book that aren't part of the story.
+
+menu-continue = Continuar
+# Each language is named in itself, so it can be found whatever the language.
+language-catalan = Català
+language-spanish = Castellano
+controls-mouse = Ratón
+controls-space-bar = [Barra espaciadora]
+
+credits-pablo = Pablo Ferrer
+credits-alex = Álex Pérez
+credits-fran = Francisco Aguirre
+credits-claudia = Claudia Mohedano
+
+story-lost = La historia se ha *perdido* entre las páginas del libro...
+back-to-menu = Volver al menú
+
+# The printed gamebook, like "Ve al apartado 12".
+gamebook-turn-to = Ve al apartado
+gamebook-the-end = Fin
+
+# The settings page of the menu.
+menu-settings = Ajustes
+settings-music-volume = Música
+settings-effects-volume = Efectos
+settings-mute = Silencio
+settings-text-pace = Velocidad del texto
+settings-font-scale = Tamaño de la letra
+settings-window = Pantalla
+settings-on = Sí
+settings-off = No
+settings-pace-slow = Lenta
+settings-pace-normal = Normal
+settings-pace-fast = Rápida
+settings-pace-instant = Instantánea
+settings-fullscreen = Completa
+settings-windowed = Ventana
+settings-back = Volver
+settings-controls = Controles
+
+# The controls page, where the actions are rebound.
+controls-change = Cambiar
+controls-press = Pulsa...
+controls-reset = Por defecto
+action-next-page = Pasar página
+action-previous-page = Volver atrás
+action-focus-next = Opción siguiente
+action-focus-prev = Opción anterior
+action-confirm = Elegir
+action-choose-1 = Elegir la primera
+action-choose-2 = Elegir la segunda
+action-choose-3 = Elegir la tercera
+action-menu = Menú
+action-skip = Acabar el texto
+binding-arrow-up = Flecha arriba
+binding-arrow-down = Flecha abajo
+binding-arrow-left = Flecha izquierda
+binding-arrow-right = Flecha derecha
+binding-space = Espacio
+binding-enter = Intro
+binding-backspace = Retroceso
+binding-page-up = RePág
+binding-page-down = AvPág
+binding-click = Clic
+binding-right-click = Clic derecho
+binding-wheel-up = Rueda arriba
+binding-wheel-down = Rueda abajo
+binding-tap = Toque
+binding-swipe-left = Deslizar a la izquierda
+binding-swipe-right = Deslizar a la derecha
+binding-dpad-up = Cruceta arriba
+binding-dpad-down = Cruceta abajo
+binding-dpad-left = Cruceta izquierda
+binding-dpad-right = Cruceta derecha
+binding-stick-forward = Palanca abajo
+binding-stick-back = Palanca arriba
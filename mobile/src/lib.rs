@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use bevy::window::WindowMode;
+use sant_jordi::settings::Settings;
 use sant_jordi::GamePlugin;
 
 #[bevy_main]
@@ -9,7 +9,7 @@ fn main() {
             DefaultPlugins.set(WindowPlugin {
                 primary_window: Some(Window {
                     resizable: false,
-                    mode: WindowMode::BorderlessFullscreen,
+                    mode: Settings::load().window_mode(),
                     ..default()
                 }),
                 ..default()
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::Action;
+use crate::localization::Localizer;
+
+/// Something the reader can press, scroll or swipe.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    WheelUp,
+    WheelDown,
+    Tap,
+    SwipeLeft,
+    SwipeRight,
+    Gamepad(GamepadButtonType),
+    /// The left stick pushed down or right.
+    StickForward,
+    /// The left stick pushed up or left.
+    StickBack,
+}
+
+/// Rebinding an action only replaces its bindings on the same device.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Device {
+    Keyboard,
+    Mouse,
+    Touch,
+    Gamepad,
+}
+
+impl Binding {
+    fn device(self) -> Device {
+        match self {
+            Binding::Key(_) => Device::Keyboard,
+            Binding::Mouse(_) | Binding::WheelUp | Binding::WheelDown => Device::Mouse,
+            Binding::Tap | Binding::SwipeLeft | Binding::SwipeRight => Device::Touch,
+            Binding::Gamepad(_) | Binding::StickForward | Binding::StickBack => Device::Gamepad,
+        }
+    }
+
+    /// Touches are too easy to make by mistake while binding, so they keep their defaults.
+    pub fn can_be_bound(self) -> bool {
+        self.device() != Device::Touch
+    }
+
+    pub fn name(self, localizer: &Localizer) -> String {
+        let key = match self {
+            Binding::Key(KeyCode::ArrowUp) => "binding-arrow-up",
+            Binding::Key(KeyCode::ArrowDown) => "binding-arrow-down",
+            Binding::Key(KeyCode::ArrowLeft) => "binding-arrow-left",
+            Binding::Key(KeyCode::ArrowRight) => "binding-arrow-right",
+            Binding::Key(KeyCode::Space) => "binding-space",
+            Binding::Key(KeyCode::Enter | KeyCode::NumpadEnter) => "binding-enter",
+            Binding::Key(KeyCode::Backspace) => "binding-backspace",
+            Binding::Key(KeyCode::PageUp) => "binding-page-up",
+            Binding::Key(KeyCode::PageDown) => "binding-page-down",
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                return ["Key", "Digit"]
+                    .into_iter()
+                    .find_map(|prefix| name.strip_prefix(prefix))
+                    .unwrap_or(&name)
+                    .to_owned();
+            }
+            Binding::Mouse(MouseButton::Left) => "binding-click",
+            Binding::Mouse(MouseButton::Right) => "binding-right-click",
+            Binding::Mouse(button) => return format!("{button:?}"),
+            Binding::WheelUp => "binding-wheel-up",
+            Binding::WheelDown => "binding-wheel-down",
+            Binding::Tap => "binding-tap",
+            Binding::SwipeLeft => "binding-swipe-left",
+            Binding::SwipeRight => "binding-swipe-right",
+            Binding::Gamepad(GamepadButtonType::DPadUp) => "binding-dpad-up",
+            Binding::Gamepad(GamepadButtonType::DPadDown) => "binding-dpad-down",
+            Binding::Gamepad(GamepadButtonType::DPadLeft) => "binding-dpad-left",
+            Binding::Gamepad(GamepadButtonType::DPadRight) => "binding-dpad-right",
+            // Printed on the buttons, at least on most gamepads.
+            Binding::Gamepad(button) => {
+                let name = match button {
+                    GamepadButtonType::South => "A",
+                    GamepadButtonType::East => "B",
+                    GamepadButtonType::West => "X",
+                    GamepadButtonType::North => "Y",
+                    GamepadButtonType::LeftTrigger => "LB",
+                    GamepadButtonType::RightTrigger => "RB",
+                    GamepadButtonType::LeftTrigger2 => "LT",
+                    GamepadButtonType::RightTrigger2 => "RT",
+                    GamepadButtonType::Start => "Start",
+                    GamepadButtonType::Select => "Select",
+                    button => return format!("{button:?}"),
+                };
+                return name.to_owned();
+            }
+            Binding::StickForward => "binding-stick-forward",
+            Binding::StickBack => "binding-stick-back",
+        };
+        localizer.ui(key)
+    }
+}
+
+/// What triggers each action.
+/// Actions missing from the settings file, like the ones added after it was written, get their
+/// default bindings.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(
+    from = "BTreeMap<Action, Vec<Binding>>",
+    into = "BTreeMap<Action, Vec<Binding>>"
+)]
+pub struct Bindings(BTreeMap<Action, Vec<Binding>>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use Binding::*;
+        let bindings = Action::ALL.into_iter().map(|action| {
+            let bindings = match action {
+                Action::NextPage => vec![
+                    Key(KeyCode::Space),
+                    Key(KeyCode::PageDown),
+                    SwipeLeft,
+                    Gamepad(GamepadButtonType::South),
+                    Gamepad(GamepadButtonType::RightTrigger),
+                ],
+                Action::PreviousPage => vec![
+                    Key(KeyCode::Backspace),
+                    Key(KeyCode::PageUp),
+                    SwipeRight,
+                    Gamepad(GamepadButtonType::East),
+                    Gamepad(GamepadButtonType::LeftTrigger),
+                ],
+                Action::FocusNext => vec![
+                    Key(KeyCode::ArrowDown),
+                    Key(KeyCode::ArrowRight),
+                    Key(KeyCode::KeyS),
+                    Key(KeyCode::KeyD),
+                    WheelDown,
+                    Gamepad(GamepadButtonType::DPadDown),
+                    Gamepad(GamepadButtonType::DPadRight),
+                    StickForward,
+                ],
+                Action::FocusPrev => vec![
+                    Key(KeyCode::ArrowUp),
+                    Key(KeyCode::ArrowLeft),
+                    Key(KeyCode::KeyW),
+                    Key(KeyCode::KeyA),
+                    WheelUp,
+                    Gamepad(GamepadButtonType::DPadUp),
+                    Gamepad(GamepadButtonType::DPadLeft),
+                    StickBack,
+                ],
+                Action::Confirm => vec![
+                    Key(KeyCode::Enter),
+                    Key(KeyCode::NumpadEnter),
+                    Key(KeyCode::Space),
+                    Gamepad(GamepadButtonType::South),
+                ],
+                Action::Choose1 => vec![Key(KeyCode::Digit1), Key(KeyCode::Numpad1)],
+                Action::Choose2 => vec![Key(KeyCode::Digit2), Key(KeyCode::Numpad2)],
+                Action::Choose3 => vec![Key(KeyCode::Digit3), Key(KeyCode::Numpad3)],
+                Action::Menu => vec![Key(KeyCode::KeyM), Gamepad(GamepadButtonType::Start)],
+                Action::Skip => vec![
+                    Key(KeyCode::Space),
+                    Mouse(MouseButton::Left),
+                    Tap,
+                    Gamepad(GamepadButtonType::South),
+                    Gamepad(GamepadButtonType::RightTrigger),
+                ],
+            };
+            (action, bindings)
+        });
+        Self(bindings.collect())
+    }
+}
+
+impl From<BTreeMap<Action, Vec<Binding>>> for Bindings {
+    fn from(mut bindings: BTreeMap<Action, Vec<Binding>>) -> Self {
+        for (action, defaults) in Bindings::default().0 {
+            bindings.entry(action).or_insert(defaults);
+        }
+        Self(bindings)
+    }
+}
+
+impl From<Bindings> for BTreeMap<Action, Vec<Binding>> {
+    fn from(bindings: Bindings) -> Self {
+        bindings.0
+    }
+}
+
+impl Bindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Replaces the bindings of the action on the device of the new one.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|bound| bound.device() != binding.device());
+        bindings.push(binding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_keeps_other_devices() {
+        let mut bindings = Bindings::default();
+        bindings.rebind(Action::Confirm, Binding::Key(KeyCode::KeyE));
+        assert_eq!(
+            bindings.get(Action::Confirm),
+            [
+                Binding::Gamepad(GamepadButtonType::South),
+                Binding::Key(KeyCode::KeyE)
+            ]
+        );
+        assert!(bindings
+            .get(Action::NextPage)
+            .contains(&Binding::Key(KeyCode::Space)));
+
+        let mut saved: BTreeMap<_, _> = bindings.clone().into();
+        saved.remove(&Action::Skip);
+        let text = ron::to_string(&saved).unwrap();
+        let loaded: Bindings = ron::from_str(&text).unwrap();
+        assert_eq!(loaded.get(Action::Confirm), bindings.get(Action::Confirm));
+        assert_eq!(
+            loaded.get(Action::Skip),
+            Bindings::default().get(Action::Skip)
+        );
+    }
+}
@@ -1,59 +1,198 @@
-use bevy::math::Vec3Swizzles;
+//! What the reader asks the book to do, whatever they use to read it.
+//! Each [`Action`] is triggered by any of its [`Binding`]s, which can be changed in the menu and
+//! are kept with the [`Settings`].
+
+#![allow(clippy::too_many_arguments)]
+
+use bevy::input::mouse::MouseWheel;
+use bevy::input::InputSystem;
 use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
 
-use crate::actions::game_control::{get_movement, GameControl};
-use crate::GameState;
+use crate::settings::Settings;
 
-mod game_control;
+pub use bindings::{Binding, Bindings};
 
-pub const FOLLOW_EPSILON: f32 = 5.;
+mod bindings;
+
+/// How far the stick has to be pushed to count as pressed.
+const STICK_THRESHOLD: f32 = 0.5;
+/// How far, in pixels, a finger has to move sideways to turn the page.
+const SWIPE_DISTANCE: f32 = 80.;
+/// Fingers moving less than this are tapping.
+const TAP_DISTANCE: f32 = 20.;
 
 pub struct ActionsPlugin;
 
-// This plugin listens for keyboard input and converts the input into Actions
-// Actions can then be used as a resource in other systems to act on the player input.
 impl Plugin for ActionsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Actions>().add_systems(
-            Update,
-            set_movement_actions.run_if(in_state(GameState::Playing)),
-        );
+        app.init_resource::<Actions>()
+            .add_systems(PreUpdate, read_actions.after(InputSystem));
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Action {
+    /// Turns to the next page, once the text has been read.
+    NextPage,
+    /// Goes back to the page before, undoing what was chosen in it.
+    PreviousPage,
+    /// Moves the focus between the choices.
+    FocusNext,
+    FocusPrev,
+    /// Picks the focused choice.
+    Confirm,
+    /// Pick the choices in their positions directly, without focusing them.
+    Choose1,
+    Choose2,
+    Choose3,
+    /// Leaves the book for the menu.
+    Menu,
+    /// Writes the whole text at once, instead of character by character.
+    Skip,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::NextPage,
+        Action::PreviousPage,
+        Action::FocusNext,
+        Action::FocusPrev,
+        Action::Confirm,
+        Action::Choose1,
+        Action::Choose2,
+        Action::Choose3,
+        Action::Menu,
+        Action::Skip,
+    ];
+
+    /// The actions that pick the choices, in the order of the choices.
+    pub const CHOOSE: [Action; 3] = [Action::Choose1, Action::Choose2, Action::Choose3];
+
+    /// Of its name in the UI.
+    pub fn key(self) -> &'static str {
+        match self {
+            Action::NextPage => "action-next-page",
+            Action::PreviousPage => "action-previous-page",
+            Action::FocusNext => "action-focus-next",
+            Action::FocusPrev => "action-focus-prev",
+            Action::Confirm => "action-confirm",
+            Action::Choose1 => "action-choose-1",
+            Action::Choose2 => "action-choose-2",
+            Action::Choose3 => "action-choose-3",
+            Action::Menu => "action-menu",
+            Action::Skip => "action-skip",
+        }
     }
 }
 
-#[derive(Default, Resource)]
+/// The actions triggered this frame.
+#[derive(Resource, Default, Debug)]
 pub struct Actions {
-    pub player_movement: Option<Vec2>,
+    triggered: HashSet<Action>,
+    /// Everything pressed this frame, bound or not, to bind it.
+    inputs: Vec<Binding>,
 }
 
-pub fn set_movement_actions(
-    mut actions: ResMut<Actions>,
+impl Actions {
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.triggered.contains(&action)
+    }
+
+    pub fn inputs(&self) -> &[Binding] {
+        &self.inputs
+    }
+
+    /// Nothing is triggered, like while an input is being bound.
+    pub fn clear(&mut self) {
+        self.triggered.clear();
+        self.inputs.clear();
+    }
+}
+
+pub fn read_actions(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    touch_input: Res<Touches>,
-    player: Query<&Transform>,
-    camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    settings: Res<Settings>,
+    mut actions: ResMut<Actions>,
+    // Which way the stick of each gamepad is pushed, `true` being forward.
+    // It counts as pressed once per push, not once per frame.
+    mut pushes: Local<HashMap<Gamepad, Option<bool>>>,
 ) {
-    let mut player_movement = Vec2::new(
-        get_movement(GameControl::Right, &keyboard_input)
-            - get_movement(GameControl::Left, &keyboard_input),
-        get_movement(GameControl::Up, &keyboard_input)
-            - get_movement(GameControl::Down, &keyboard_input),
+    let mut inputs: Vec<Binding> = keyboard_input
+        .get_just_pressed()
+        .map(|&key| Binding::Key(key))
+        .collect();
+    inputs.extend(
+        mouse_input
+            .get_just_pressed()
+            .map(|&button| Binding::Mouse(button)),
     );
-
-    if let Some(touch_position) = touch_input.first_pressed_position() {
-        let (camera, camera_transform) = camera.single();
-        if let Some(touch_position) = camera.viewport_to_world_2d(camera_transform, touch_position)
-        {
-            let diff = touch_position - player.single().translation.xy();
-            if diff.length() > FOLLOW_EPSILON {
-                player_movement = diff.normalize();
+    let scrolled: f32 = mouse_wheel.read().map(|wheel| wheel.y).sum();
+    if scrolled > 0. {
+        inputs.push(Binding::WheelUp);
+    } else if scrolled < 0. {
+        inputs.push(Binding::WheelDown);
+    }
+    for touch in touches.iter_just_released() {
+        let moved = touch.distance();
+        if moved.x.abs() > SWIPE_DISTANCE && moved.x.abs() > moved.y.abs() {
+            inputs.push(if moved.x < 0. {
+                Binding::SwipeLeft
+            } else {
+                Binding::SwipeRight
+            });
+        } else if moved.length() < TAP_DISTANCE {
+            inputs.push(Binding::Tap);
+        }
+    }
+    for gamepad in gamepads.iter() {
+        inputs.extend(
+            gamepad_buttons
+                .get_just_pressed()
+                .filter(|button| button.gamepad == gamepad)
+                .map(|button| Binding::Gamepad(button.button_type)),
+        );
+        let axis = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+        let (x, y) = (
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+        let push = if y < -STICK_THRESHOLD || x > STICK_THRESHOLD {
+            Some(true)
+        } else if y > STICK_THRESHOLD || x < -STICK_THRESHOLD {
+            Some(false)
+        } else {
+            None
+        };
+        let previous = pushes.insert(gamepad, push).flatten();
+        if push != previous {
+            match push {
+                Some(true) => inputs.push(Binding::StickForward),
+                Some(false) => inputs.push(Binding::StickBack),
+                None => {}
             }
         }
     }
 
-    if player_movement != Vec2::ZERO {
-        actions.player_movement = Some(player_movement.normalize());
-    } else {
-        actions.player_movement = None;
-    }
+    actions.triggered = Action::ALL
+        .into_iter()
+        .filter(|&action| {
+            settings
+                .bindings
+                .get(action)
+                .iter()
+                .any(|binding| inputs.contains(binding))
+        })
+        .collect();
+    actions.inputs = inputs;
 }
@@ -0,0 +1,35 @@
+//! Plays the book in the terminal, to try the story out without the game.
+//!
+//! It reads the story from `assets/story/book.story.ron` and the translations from
+//! `assets/locales`, so run it from the root of the repository.
+//!
+//! - `cargo run --bin play`: in the language the story is written in.
+//! - `cargo run --bin play -- ca`: in the language with that code.
+
+use std::{env, io, process::ExitCode};
+
+use sant_jordi::{
+    story::{BOOK_PATH, LOCALES_PATH},
+    terminal::Player,
+};
+
+const USAGE: &str = "Usage: play [language]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let language = match args.as_slice() {
+        [] => None,
+        [code] => Some(code.as_str()),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let played = Player::load(BOOK_PATH, LOCALES_PATH, language)
+        .and_then(|mut player| player.play(io::stdin().lock(), io::stdout()));
+    if let Err(error) = played {
+        eprintln!("{error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
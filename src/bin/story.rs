@@ -0,0 +1,121 @@
+//! Tools for the people writing the story.
+//!
+//! They read the story from `assets/story/book.story.ron`, so run them from the root of the repository.
+//!
+//! - `cargo run --bin story -- dot [file]`: Graphviz picture of the story graph.
+//!   Render it with `dot -Tsvg story.dot -o story.svg`.
+//! - `cargo run --bin story -- lint`: authoring mistakes in the story and its translations.
+//!   Fails if there's any, except for choices without an illustration: a few choices of the book
+//!   are only text on purpose, so those are just warnings.
+//! - `cargo run --bin story -- stats`: length of the story, in words and reading time.
+//! - `cargo run --bin story -- gamebook <file> [language]`: the story as a gamebook to print,
+//!   in Markdown or in HTML depending on the extension of the file, like `gamebook.html`.
+//!   The illustrations are linked with their full path, so keep the repository around.
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use sant_jordi::{
+    dot,
+    gamebook::Gamebook,
+    lint,
+    stats::Stats,
+    story::{StoryFile, ASSETS_PATH, BOOK_PATH, LOCALES_PATH},
+};
+
+const USAGE: &str =
+    "Usage: story dot [file] | story lint | story stats | story gamebook <file.md|file.html> [language]
+`story lint` fails on any mistake, but only warns about choices without an illustration.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let book_to_dot = || {
+        dot::book_to_dot(BOOK_PATH).map_err(|error| eprintln!("Couldn't read {BOOK_PATH}: {error}"))
+    };
+    match args.as_slice() {
+        ["dot"] => match book_to_dot() {
+            Ok(dot) => print!("{dot}"),
+            Err(()) => return ExitCode::FAILURE,
+        },
+        ["dot", path] => {
+            let Ok(dot) = book_to_dot() else {
+                return ExitCode::FAILURE;
+            };
+            if let Err(error) = fs::write(path, dot) {
+                eprintln!("Couldn't write {path}: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+        ["lint"] => {
+            let lints = lint::lint_book(
+                Path::new(BOOK_PATH),
+                Path::new(LOCALES_PATH),
+                Path::new(ASSETS_PATH),
+            );
+            let (warnings, problems): (Vec<_>, Vec<_>) =
+                lints.iter().partition(|lint| lint.problem.is_warning());
+            for lint in warnings.iter() {
+                eprintln!("warning: {lint}");
+            }
+            for lint in problems.iter() {
+                eprintln!("{lint}");
+            }
+            if !warnings.is_empty() {
+                eprintln!("{} warnings", warnings.len());
+            }
+            if !problems.is_empty() {
+                eprintln!("{} problems found", problems.len());
+                return ExitCode::FAILURE;
+            }
+        }
+        ["stats"] => {
+            let stats = fs::read_to_string(BOOK_PATH)
+                .map_err(|error| error.to_string())
+                .and_then(|text| StoryFile::from_ron(&text).map_err(|error| error.to_string()))
+                .and_then(|story| Stats::new(&story).map_err(|error| error.to_string()));
+            match stats {
+                Ok(stats) => print!("{stats}"),
+                Err(error) => {
+                    eprintln!("Couldn't read {BOOK_PATH}: {error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        ["gamebook", path, language @ ..] if language.len() <= 1 => {
+            let html = match Path::new(path)
+                .extension()
+                .and_then(|extension| extension.to_str())
+            {
+                Some("md") => false,
+                Some("html") => true,
+                _ => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let images = fs::canonicalize(ASSETS_PATH).unwrap_or_else(|_| ASSETS_PATH.into());
+            let gamebook =
+                match Gamebook::load(BOOK_PATH, LOCALES_PATH, language.first().copied(), &images) {
+                    Ok(gamebook) => gamebook,
+                    Err(error) => {
+                        eprintln!("Couldn't make the gamebook: {error}");
+                        return ExitCode::FAILURE;
+                    }
+                };
+            let text = if html {
+                gamebook.to_html()
+            } else {
+                gamebook.to_markdown()
+            };
+            if let Err(error) = fs::write(path, text) {
+                eprintln!("Couldn't write {path}: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -1,29 +1,41 @@
 #![allow(clippy::too_many_arguments)]
 
-use std::iter;
-
-use bevy::{log, prelude::*};
+use bevy::{ecs::system::SystemParam, log, prelude::*};
 use bevy_kira_audio::prelude::*;
 
 use crate::{
-    book_content::{self, BookGraph, TextStyles},
-    graph::Node,
-    loading::{AnimationAssets, AudioAssets, FontAssets, Illustrations, UiTextures},
+    actions::{Action, Actions},
+    book_content::{BookGraph, TextStyles},
+    cues::{self, PendingCues, PlayCues},
+    focus::{self, ChoiceFocus, ConfirmChoice},
+    graph::{GraphError, Node},
+    loading::{AnimationAssets, AudioAssets, FontAssets, StoryAssets, UiTextures},
+    localization::{self, Localizer},
+    markup::{self, Palette},
     menu::{FirstPage, SecondPage},
-    utils, GameState,
+    pagination::{self, NextPages, Paginated},
+    save::{self, SaveData},
+    settings::Settings,
+    story::{self, Story},
+    typewriter::{self, TextSpeed, Typewriter},
+    GameState,
 };
 
 pub const BUTTON_HOVER_COLOR: Color = Color::rgba(1., 0., 0., 0.5);
 pub const BUTTON_NORMAL_COLOR: Color = Color::NONE;
 pub const BUTTON_PRESSED_COLOR: Color = Color::rgba(0.7, 0., 0., 0.7);
+/// Greys out the text and tints the illustration of locked choices.
+pub const LOCKED_CHOICE_COLOR: Color = Color::rgba(0.4, 0.4, 0.4, 0.6);
 
-pub fn default_text_styles(fonts: &Res<FontAssets>, too_many_options: bool) -> TextStyles {
-    let normal_font_size = if too_many_options { 25. } else { 30. };
+/// Texts that don't fit in their page get smaller, see [`pagination`].
+/// The scale is the one chosen in the [`Settings`].
+pub fn default_text_styles(fonts: &FontAssets, font_scale: f32) -> TextStyles {
+    let normal_font_size = 30. * font_scale;
     TextStyles {
         first_letter: TextStyle {
             color: Color::rgb(0.235, 0.039, 0.337),
             font: fonts.first_letter.clone(),
-            font_size: 100.,
+            font_size: 100. * font_scale,
         },
         normal: TextStyle {
             color: Color::hex("3a1e0d").unwrap(),
@@ -35,18 +47,35 @@ pub fn default_text_styles(fonts: &Res<FontAssets>, too_many_options: bool) -> T
             font_size: normal_font_size,
             color: Color::rgb(0.678, 0.047, 0.109),
         },
+        // There's no italic Seagram, so italics are written in a lighter ink instead.
+        italic: TextStyle {
+            color: Color::hex("7a5a3f").unwrap(),
+            font: fonts.normal.clone(),
+            font_size: normal_font_size,
+        },
     }
 }
 
 pub struct BookPlugin;
 impl Plugin for BookPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<Transition>()
+        app.init_resource::<Palette>()
+            .init_resource::<NextPages>()
+            .init_resource::<TextSpeed>()
+            .init_resource::<PendingCues>()
+            .init_resource::<ChoiceFocus>()
+            .add_event::<Transition>()
             .add_event::<AdvanceSimpleNode>()
             .add_event::<EraseEverything>()
             .add_event::<OptionChosen>()
             .add_event::<ShowArrow>()
+            .add_event::<ShowBackArrow>()
+            .add_event::<GoBack>()
+            .add_event::<StoryLost>()
             .add_event::<GameEnded>()
+            .add_event::<TurnPage>()
+            .add_event::<PlayCues>()
+            .add_event::<ConfirmChoice>()
             .add_systems(OnEnter(GameState::Playing), (setup_graph, setup_lifecycle))
             .add_systems(
                 Update,
@@ -57,14 +86,54 @@ impl Plugin for BookPlugin {
                     erase_everything_listener,
                     draw_chosen_option,
                     show_arrow_system,
+                    show_back_arrow_system,
                     advance_simple_node_listener,
                     end_game_listener,
                     flip_page,
                     flip_page_listener,
                     interact_with_end_button,
                     interact_with_arrow,
+                    interact_with_back_arrow,
+                    go_back_listener,
+                    interact_with_back_to_menu_button,
+                    open_menu,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    pagination::fit_texts_in_pages,
+                    typewriter::write_texts,
+                    pagination::hide_rest_of_spread,
+                    // Last, so the new text of the page is measured once it's been laid out.
+                    turn_page_listener,
+                )
+                    .chain()
+                    // Clicking or pressing space while writing only finishes the text.
+                    .after(flip_page)
+                    .after(interact_with_arrow)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, cues::play_cues.run_if(in_state(GameState::Playing)))
+            .add_systems(
+                Update,
+                (
+                    (focus::move_focus, focus::hover_choices)
+                        .chain()
+                        .before(focus::highlight_focused_choice),
+                    focus::highlight_focused_choice,
+                    // Before the same input finishes the text and shows the choices.
+                    focus::confirm_focused_choice
+                        .before(typewriter::write_texts)
+                        .before(interact_with_options),
                 )
                     .run_if(in_state(GameState::Playing)),
+            )
+            // After `Update` so everything spawned for the broken page can be erased.
+            .add_systems(
+                PostUpdate,
+                story_lost_listener.run_if(in_state(GameState::Playing)),
             );
     }
 }
@@ -91,9 +160,61 @@ pub struct Transition;
 #[derive(Event, Default)]
 pub struct ShowArrow;
 
+#[derive(Event, Default)]
+pub struct ShowBackArrow;
+
+#[derive(Event, Default)]
+pub struct GoBack;
+
+/// Goes on reading the text of the page, in the next spread.
+#[derive(Event, Default)]
+pub struct TurnPage;
+
+/// The graph couldn't move on, the book can't go on either.
+#[derive(Event)]
+pub struct StoryLost(pub GraphError);
+
+/// The fonts of the book, with the texts as big as the reader wants them.
+#[derive(SystemParam)]
+pub struct Fonts<'w> {
+    assets: Res<'w, FontAssets>,
+    settings: Res<'w, Settings>,
+}
+
+impl<'w> Fonts<'w> {
+    pub fn text_styles(&self) -> TextStyles {
+        default_text_styles(&self.assets, self.settings.font_scale)
+    }
+
+    /// Scaled like the rest of the texts.
+    pub fn size(&self, font_size: f32) -> f32 {
+        font_size * self.settings.font_scale
+    }
+}
+
+impl<'w> std::ops::Deref for Fonts<'w> {
+    type Target = FontAssets;
+
+    fn deref(&self) -> &FontAssets {
+        &self.assets
+    }
+}
+
+/// The two pages of the open book.
+#[derive(SystemParam)]
+pub struct Pages<'w, 's> {
+    first: Query<'w, 's, Entity, With<FirstPage>>,
+    second: Query<'w, 's, Entity, With<SecondPage>>,
+}
+
 #[derive(Resource)]
 pub struct LifecycleManager(pub Lifecycle);
 
+/// Index of the node shown on the current page.
+/// The graph might have already moved on from it, after advancing or choosing.
+#[derive(Resource, Default)]
+pub struct CurrentPage(pub usize);
+
 #[derive(Resource)]
 pub enum Lifecycle {
     ShowNode,
@@ -102,6 +223,8 @@ pub enum Lifecycle {
     Transitioning,
     SimpleNode,
     End,
+    /// Something went wrong with the graph.
+    Lost,
 }
 
 impl Lifecycle {
@@ -114,42 +237,61 @@ impl Lifecycle {
             Transitioning => ShowNode,
             SimpleNode => Transitioning,
             End => ShowNode,
+            Lost => Lost,
         }
     }
 }
 
 fn setup_lifecycle(mut commands: Commands) {
     commands.insert_resource(LifecycleManager(Lifecycle::ShowNode));
+    commands.init_resource::<CurrentPage>();
+    commands.insert_resource(NextPages::default());
+    commands.insert_resource(PendingCues::default());
 }
 
 fn draw_chosen_option(
     mut commands: Commands,
     first_page: Query<Entity, With<FirstPage>>,
     second_page: Query<Entity, With<SecondPage>>,
-    fonts: Res<FontAssets>,
+    fonts: Fonts,
     mut events: EventReader<OptionChosen>,
     mut graph: ResMut<BookGraph>,
+    mut story_lost: EventWriter<StoryLost>,
+    localizer: Localizer,
+    palette: Res<Palette>,
+    mut play_cues: EventWriter<PlayCues>,
 ) {
     for event in events.read() {
-        let current_node = graph.get_current_node();
-        let Node::Fork {
-            choices, content, ..
-        } = current_node
-        else {
-            unreachable!("An option was chosen, it's a fork.");
-        };
-        // TODO: I could get everything from `current_node`.
+        // TODO: I could get everything from the current node.
         let OptionChosen { index, text, image } = event;
-        let chosen_option = &choices[*index];
+        let node_index = graph.get_current_index();
+        let chosen = graph.try_get_current_node().and_then(|node| match node {
+            Node::Fork {
+                choices, content, ..
+            } => choices.get(*index).map(|choice| (content, choice)).ok_or(
+                GraphError::ChoiceOutOfRange {
+                    node: node_index,
+                    choice: *index,
+                    choices: choices.len(),
+                },
+            ),
+            Node::Simple { .. } => Err(GraphError::NotFork(node_index)),
+        });
+        let (content, chosen_option) = match chosen {
+            Ok(chosen) => chosen,
+            Err(error) => {
+                story_lost.send(StoryLost(error));
+                continue;
+            }
+        };
+        play_cues.send(PlayCues(chosen_option.sounds.clone()));
         let mut first_page = commands.entity(first_page.single());
+        let text_styles = content.text_styles(&fonts, &palette);
         first_page.with_children(|parent| {
             parent.spawn((
-                get_formatted_text(
-                    text,
-                    &content
-                        .text_styles
-                        .clone()
-                        .unwrap_or(default_text_styles(&fonts, false)),
+                pagination::paginated(
+                    get_formatted_text(text, &text_styles, &palette),
+                    &text_styles,
                 ),
                 Erasable,
             ));
@@ -158,10 +300,14 @@ fn draw_chosen_option(
         second_page.with_children(|parent| {
             parent.spawn((
                 TextBundle::from_section(
-                    (chosen_option.additional_text)(&graph.context),
+                    localizer.text(
+                        &localization::choice_key(node_index, *index, "additional-text"),
+                        &chosen_option.additional_text,
+                        &graph.context,
+                    ),
                     TextStyle {
                         font: fonts.normal.clone(),
-                        font_size: 30.,
+                        font_size: fonts.size(30.),
                         color: Color::BLACK,
                     },
                 ),
@@ -181,7 +327,9 @@ fn draw_chosen_option(
                 ));
             }
         });
-        graph.choose(*index);
+        if let Err(error) = graph.try_choose(*index) {
+            story_lost.send(StoryLost(error));
+        }
     }
 }
 
@@ -189,11 +337,13 @@ fn erase_everything_listener(
     mut commands: Commands,
     erasable_query: Query<Entity, With<Erasable>>,
     mut events: EventReader<EraseEverything>,
+    mut next_pages: ResMut<NextPages>,
 ) {
     for _ in events.read() {
         for entity in erasable_query.iter() {
             commands.get_entity(entity).unwrap().despawn_recursive();
         }
+        next_pages.clear();
     }
 }
 
@@ -206,17 +356,27 @@ fn end_game_listener(mut events: EventReader<GameEnded>, mut lifecycle: ResMut<L
 
 fn flip_page(
     lifecycle: Res<LifecycleManager>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    actions: Res<Actions>,
     mut players: Query<&mut AnimationPlayer>,
     animations: Res<AnimationAssets>,
     mut event_writer: EventWriter<Transition>,
     mut erase_everything: EventWriter<EraseEverything>,
     audio: Res<Audio>,
     audio_assets: Res<AudioAssets>,
+    next_pages: Res<NextPages>,
+    mut turn_page: EventWriter<TurnPage>,
+    typewriters: Query<&Typewriter>,
+    graph: Res<BookGraph>,
+    current_page: Res<CurrentPage>,
+    mut play_cues: EventWriter<PlayCues>,
 ) {
-    if matches!(lifecycle.0, Lifecycle::Chosen | Lifecycle::SimpleNode)
-        && keyboard_input.just_pressed(KeyCode::Space)
-    {
+    // While writing, it only finishes the text.
+    if !actions.just_pressed(Action::NextPage) || typewriters.iter().any(Typewriter::is_writing) {
+        return;
+    }
+    if next_pages.has_more() {
+        turn_page.send_default();
+    } else if matches!(lifecycle.0, Lifecycle::Chosen | Lifecycle::SimpleNode) {
         do_flip_page(
             &audio,
             &audio_assets,
@@ -224,10 +384,14 @@ fn flip_page(
             &animations,
             &mut event_writer,
             &mut erase_everything,
+            &graph,
+            &current_page,
+            &mut play_cues,
         );
     }
 }
 
+/// Going on from a simple node plays its cues.
 fn do_flip_page(
     audio: &Res<Audio>,
     audio_assets: &Res<AudioAssets>,
@@ -235,8 +399,14 @@ fn do_flip_page(
     animations: &Res<AnimationAssets>,
     event_writer: &mut EventWriter<Transition>,
     erase_everything: &mut EventWriter<EraseEverything>,
+    graph: &BookGraph,
+    current_page: &CurrentPage,
+    play_cues: &mut EventWriter<PlayCues>,
 ) {
     audio.play(audio_assets.page_flip.clone());
+    if let Some(Node::Simple { extra, .. }) = graph.get_node(current_page.0) {
+        play_cues.send(PlayCues(extra.sounds.clone()));
+    }
     for mut player in players.iter_mut() {
         player.start(animations.page_flip.clone());
         event_writer.send_default();
@@ -244,12 +414,61 @@ fn do_flip_page(
     }
 }
 
+fn turn_page_listener(
+    mut events: EventReader<TurnPage>,
+    mut next_pages: ResMut<NextPages>,
+    mut texts: Query<(&mut Text, &mut Visibility), With<Paginated>>,
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut players: Query<&mut AnimationPlayer>,
+    animations: Res<AnimationAssets>,
+) {
+    for _ in events.read() {
+        if !next_pages.has_more() {
+            continue;
+        }
+        audio.play(audio_assets.page_flip.clone());
+        for mut player in players.iter_mut() {
+            player.start(animations.page_flip.clone());
+        }
+        let sections = next_pages.take();
+        for (mut text, mut visibility) in texts.iter_mut() {
+            text.sections = sections.clone();
+            // Until it's known whether it fits.
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+fn do_flip_page_backwards(
+    audio: &Res<Audio>,
+    audio_assets: &Res<AudioAssets>,
+    players: &mut Query<&mut AnimationPlayer>,
+    animations: &Res<AnimationAssets>,
+    clips: &Res<Assets<AnimationClip>>,
+) {
+    audio.play(audio_assets.page_flip.clone());
+    let duration = clips
+        .get(&animations.page_flip)
+        .map(|clip| clip.duration())
+        .unwrap_or_default();
+    for mut player in players.iter_mut() {
+        player
+            .start(animations.page_flip.clone())
+            .set_speed(-1.)
+            .seek_to(duration);
+    }
+}
+
 fn advance_simple_node_listener(
     mut events: EventReader<AdvanceSimpleNode>,
     mut graph: ResMut<BookGraph>,
+    mut story_lost: EventWriter<StoryLost>,
 ) {
     for _ in events.read() {
-        graph.advance();
+        if let Err(error) = graph.try_advance() {
+            story_lost.send(StoryLost(error));
+        }
     }
 }
 
@@ -264,8 +483,8 @@ fn flip_page_listener(
         for player in players.iter() {
             if player.is_finished() {
                 event_writer.send_default();
-                match graph.get_current_node() {
-                    Node::Simple { next, .. } if next.is_some() => {
+                match graph.try_get_current_node() {
+                    Ok(Node::Simple { next, .. }) if next.is_some() => {
                         show_arrow.send_default();
                     }
                     _ => {}
@@ -289,16 +508,27 @@ fn show_current_node_and_transition(
     mut lifecycle: ResMut<LifecycleManager>,
     mut transition: EventWriter<Transition>,
     mut advance_simple_node: EventWriter<AdvanceSimpleNode>,
-    first_page: Query<Entity, With<FirstPage>>,
-    second_page: Query<Entity, With<SecondPage>>,
+    pages: Pages,
     mut commands: Commands,
-    fonts: Res<FontAssets>,
+    fonts: Fonts,
     textures: Res<UiTextures>,
+    asset_server: Res<AssetServer>,
+    localizer: Localizer,
+    palette: Res<Palette>,
     mut game_ended: EventWriter<GameEnded>,
+    mut current_page: ResMut<CurrentPage>,
+    mut show_back_arrow: EventWriter<ShowBackArrow>,
+    mut story_lost: EventWriter<StoryLost>,
+    mut play_cues: EventWriter<PlayCues>,
 ) {
     if let Lifecycle::ShowNode = lifecycle.0 {
-        let first_page = first_page.single();
-        let second_page = second_page.single();
+        current_page.0 = graph.get_current_index();
+        save::save(&graph);
+        if graph.can_go_back() {
+            show_back_arrow.send_default();
+        }
+        let first_page = pages.first.single();
+        let second_page = pages.second.single();
         let is_simple = show_current_node(
             &graph,
             first_page,
@@ -306,51 +536,68 @@ fn show_current_node_and_transition(
             &mut commands,
             &fonts,
             &textures,
+            &asset_server,
+            &localizer,
+            &palette,
             &mut game_ended,
         );
-        if is_simple {
-            lifecycle.0 = Lifecycle::SimpleNode;
-            advance_simple_node.send_default();
-        } else {
-            transition.send_default();
+        if let Ok(Node::Simple { content, .. } | Node::Fork { content, .. }) =
+            graph.try_get_current_node()
+        {
+            play_cues.send(PlayCues(content.sounds.clone()));
+        }
+        match is_simple {
+            Ok(true) => {
+                lifecycle.0 = Lifecycle::SimpleNode;
+                advance_simple_node.send_default();
+            }
+            Ok(false) => {
+                transition.send_default();
+            }
+            Err(error) => {
+                story_lost.send(StoryLost(error));
+            }
         }
     }
 }
 
+/// Choices are picked by clicking them or with [`ConfirmChoice`].
 fn interact_with_options(
     lifecycle: Res<LifecycleManager>,
-    mut interaction_query: Query<
-        (&Interaction, &ChoicesOption, &mut BackgroundColor),
-        Changed<Interaction>,
-    >,
+    mut interaction_query: Query<(Ref<Interaction>, &ChoicesOption, &mut BackgroundColor)>,
+    mut confirm_choice: EventReader<ConfirmChoice>,
     mut option_chosen: EventWriter<OptionChosen>,
     mut transition: EventWriter<Transition>,
     mut erase_everything: EventWriter<EraseEverything>,
     mut show_arrow: EventWriter<ShowArrow>,
+    mut show_back_arrow: EventWriter<ShowBackArrow>,
     audio: Res<Audio>,
     audio_assets: Res<AudioAssets>,
 ) {
+    let confirmed: Vec<usize> = confirm_choice
+        .read()
+        .map(|ConfirmChoice(position)| *position)
+        .collect();
     if let Lifecycle::Choosing = lifecycle.0 {
         for (interaction, choice, mut background_color) in interaction_query.iter_mut() {
-            match *interaction {
-                Interaction::Hovered => {
-                    *background_color = BUTTON_HOVER_COLOR.into();
-                }
-                Interaction::None => {
-                    *background_color = BUTTON_NORMAL_COLOR.into();
-                }
-                Interaction::Pressed => {
-                    audio.play(audio_assets.scribble.clone()).with_volume(5.);
-                    *background_color = BUTTON_PRESSED_COLOR.into();
-                    option_chosen.send(OptionChosen {
-                        index: choice.index,
-                        text: choice.text.clone(),
-                        image: choice.image.clone(),
-                    });
-                    erase_everything.send_default();
-                    transition.send_default();
-                    show_arrow.send_default();
-                }
+            if choice.locked {
+                continue;
+            }
+            let pressed = interaction.is_changed() && *interaction == Interaction::Pressed;
+            if pressed || confirmed.contains(&choice.position) {
+                audio.play(audio_assets.scribble.clone()).with_volume(5.);
+                *background_color = BUTTON_PRESSED_COLOR.into();
+                option_chosen.send(OptionChosen {
+                    index: choice.index,
+                    text: choice.text.clone(),
+                    image: choice.image.clone(),
+                });
+                erase_everything.send_default();
+                transition.send_default();
+                show_arrow.send_default();
+                show_back_arrow.send_default();
+                // Only one of them.
+                break;
             }
         }
     }
@@ -364,7 +611,12 @@ fn show_arrow_system(
     textures: Res<UiTextures>,
     mut events: EventReader<ShowArrow>,
     second_page: Query<Entity, With<SecondPage>>,
+    lifecycle: Res<LifecycleManager>,
 ) {
+    if let Lifecycle::Lost = lifecycle.0 {
+        events.clear();
+        return;
+    }
     for _ in events.read() {
         commands
             .entity(second_page.single())
@@ -395,11 +647,61 @@ fn show_arrow_system(
     }
 }
 
+#[derive(Component)]
+pub struct BackArrow;
+
+fn show_back_arrow_system(
+    mut commands: Commands,
+    textures: Res<UiTextures>,
+    mut events: EventReader<ShowBackArrow>,
+    first_page: Query<Entity, With<FirstPage>>,
+    lifecycle: Res<LifecycleManager>,
+) {
+    if let Lifecycle::Lost = lifecycle.0 {
+        events.clear();
+        return;
+    }
+    for _ in events.read() {
+        commands
+            .entity(first_page.single())
+            .with_children(|parent| {
+                let mut flipped_arrow: UiImage = textures.arrow.clone().into();
+                flipped_arrow.flip_x = true;
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                bottom: Val::Px(0.),
+                                left: Val::Px(0.),
+                                height: Val::Px(80.),
+                                ..default()
+                            },
+                            background_color: Color::NONE.into(),
+                            ..default()
+                        },
+                        Erasable,
+                        BackArrow,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            image: flipped_arrow,
+                            ..default()
+                        });
+                    });
+            });
+    }
+}
+
 #[derive(Component)]
 pub struct ChoicesOption {
     pub index: usize,
+    /// Where it's shown, from the top.
+    pub position: usize,
     pub image: Option<Handle<Image>>,
     pub text: String,
+    /// Locked choices are shown but can't be picked.
+    pub locked: bool,
 }
 
 #[derive(Component)]
@@ -411,30 +713,47 @@ pub struct Erasable;
 #[derive(Component)]
 pub struct EndButton;
 
+#[derive(Component)]
+pub struct BackToMenuButton;
+
 /// Returns whether or not the node is simple.
+/// Fails, without showing anything, if the node doesn't exist or it's a fork with every
+/// choice hidden or locked.
 fn show_current_node(
     graph: &BookGraph,
     first_page: Entity,
     second_page: Entity,
     commands: &mut Commands,
-    fonts: &Res<FontAssets>,
+    fonts: &Fonts,
     textures: &Res<UiTextures>,
+    asset_server: &AssetServer,
+    localizer: &Localizer,
+    palette: &Palette,
     game_ended: &mut EventWriter<GameEnded>,
-) -> bool {
-    let node = graph.get_current_node();
+) -> Result<bool, GraphError> {
+    let node = graph.try_get_playable_node()?;
+    let node_index = graph.get_current_index();
     match node {
         Node::Fork { content, choices } => {
-            let text = (content.text)(&graph.context);
-            let number_of_choices = choices.len();
+            let text = localizer.text(
+                &localization::node_key(node_index, "text"),
+                &content.text,
+                &graph.context,
+            );
+            let shown_choices: Vec<_> = choices
+                .iter()
+                .enumerate()
+                .filter(|(_, choice)| choice.is_shown(&graph.context))
+                .collect();
+            let text_styles = content.text_styles(fonts, palette);
+            let choice_text_styles = fonts.text_styles();
             commands.entity(first_page).with_children(|parent| {
                 parent.spawn((
-                    get_formatted_text(
-                        text,
-                        &content
-                            .text_styles
-                            .clone()
-                            .unwrap_or(default_text_styles(&fonts, false)),
+                    pagination::paginated(
+                        get_formatted_text(&text, &text_styles, palette),
+                        &text_styles,
                     ),
+                    Typewriter::default(),
                     Erasable,
                 ));
                 parent.spawn((
@@ -450,8 +769,26 @@ fn show_current_node(
                 ));
             });
             commands.entity(second_page).with_children(|parent| {
-                for (index, choice) in choices.iter().enumerate() {
-                    let text = (choice.text)(&graph.context).to_string();
+                // `position` is where the choice goes, `index` is which one it is.
+                for (position, (index, choice)) in shown_choices.into_iter().enumerate() {
+                    let text = localizer
+                        .text(
+                            &localization::choice_key(node_index, index, "text"),
+                            &choice.text,
+                            &graph.context,
+                        )
+                        .to_string();
+                    let locked_reason = choice.locked_reason(&graph.context).map(|reason| {
+                        localizer.text(
+                            &localization::choice_key(node_index, index, "locked-reason"),
+                            reason,
+                            &graph.context,
+                        )
+                    });
+                    let illustration: Option<Handle<Image>> = choice
+                        .illustration
+                        .as_deref()
+                        .map(|name| asset_server.load(story::illustration_path(name)));
                     parent
                         .spawn((
                             ButtonBundle {
@@ -462,7 +799,11 @@ fn show_current_node(
                                     flex_direction: FlexDirection::Row,
                                     position_type: PositionType::Relative,
                                     align_items: AlignItems::Center,
-                                    margin: UiRect::top(Val::Px(if index == 0 { 0. } else { 10. })),
+                                    margin: UiRect::top(Val::Px(if position == 0 {
+                                        0.
+                                    } else {
+                                        10.
+                                    })),
                                     padding: UiRect::all(Val::Px(5.)),
                                     ..default()
                                 },
@@ -470,8 +811,10 @@ fn show_current_node(
                             },
                             ChoicesOption {
                                 index,
-                                image: choice.illustration.clone(),
+                                position,
+                                image: illustration.clone(),
                                 text: text.clone(),
+                                locked: locked_reason.is_some(),
                             },
                             Erasable,
                         ))
@@ -491,39 +834,52 @@ fn show_current_node(
                                 },
                                 Erasable,
                             ));
-                            let image = if let Some(ref illustration) = choice.illustration {
+                            let image = if let Some(illustration) = illustration {
                                 Some(ImageBundle {
-                                    image: illustration.clone().into(),
+                                    image: illustration.into(),
                                     style: Style {
                                         height: Val::Px(150.),
                                         ..default()
                                     },
+                                    background_color: if locked_reason.is_some() {
+                                        LOCKED_CHOICE_COLOR.into()
+                                    } else {
+                                        Color::WHITE.into()
+                                    },
                                     ..default()
                                 })
                             } else {
                                 None
                             };
-                            let sections = utils::process_string_asterisks(&text)
-                                .into_iter()
-                                .enumerate()
-                                .map(|(index, string)| TextSection {
-                                    value: string,
-                                    style: if index % 2 == 0 {
-                                        default_text_styles(&fonts, number_of_choices == 3).normal
-                                    } else {
-                                        default_text_styles(&fonts, number_of_choices == 3)
-                                            .highlighted
+                            let mut sections = markup_sections(&text, &choice_text_styles, palette);
+                            if let Some(reason) = locked_reason {
+                                for section in sections.iter_mut() {
+                                    section.style.color = LOCKED_CHOICE_COLOR;
+                                }
+                                sections.push(TextSection {
+                                    value: format!("\n{}", reason),
+                                    style: TextStyle {
+                                        font: fonts.normal.clone(),
+                                        font_size: fonts.size(20.),
+                                        color: LOCKED_CHOICE_COLOR,
                                     },
                                 });
+                            }
 
-                            if index % 2 == 0 {
+                            if position % 2 == 0 {
                                 if let Some(image) = image {
                                     parent.spawn(image);
                                 }
 
-                                parent.spawn(TextBundle::from_sections(sections));
+                                parent.spawn((
+                                    TextBundle::from_sections(sections),
+                                    pagination::fits_in_page(&choice_text_styles),
+                                ));
                             } else {
-                                parent.spawn(TextBundle::from_sections(sections));
+                                parent.spawn((
+                                    TextBundle::from_sections(sections),
+                                    pagination::fits_in_page(&choice_text_styles),
+                                ));
 
                                 if let Some(image) = image {
                                     parent.spawn(image);
@@ -532,7 +888,7 @@ fn show_current_node(
                         });
                 }
             });
-            false
+            Ok(false)
         }
         Node::Simple {
             content,
@@ -543,15 +899,19 @@ fn show_current_node(
                 log::info!("Sending GameEnded event");
                 game_ended.send_default();
             }
+            let text = localizer.text(
+                &localization::node_key(node_index, "text"),
+                &content.text,
+                &graph.context,
+            );
+            let text_styles = content.text_styles(fonts, palette);
             commands.entity(first_page).with_children(|parent| {
                 parent.spawn((
-                    get_formatted_text(
-                        (content.text)(&graph.context),
-                        &content
-                            .text_styles
-                            .clone()
-                            .unwrap_or(default_text_styles(&fonts, false)),
+                    pagination::paginated(
+                        get_formatted_text(&text, &text_styles, palette),
+                        &text_styles,
                     ),
+                    Typewriter::default(),
                     Erasable,
                 ));
             });
@@ -608,10 +968,14 @@ fn show_current_node(
                         });
                         parent.spawn((
                             TextBundle::from_section(
-                                (extra.additional_text)(&graph.context),
+                                localizer.text(
+                                    &localization::node_key(node_index, "additional-text"),
+                                    &extra.additional_text,
+                                    &graph.context,
+                                ),
                                 TextStyle {
                                     font: fonts.normal.clone(),
-                                    font_size: 30.,
+                                    font_size: fonts.size(30.),
                                     color: Color::BLACK,
                                 },
                             ),
@@ -621,7 +985,9 @@ fn show_current_node(
                 if let Some(ref illustration) = extra.illustration {
                     parent.spawn((
                         ImageBundle {
-                            image: illustration.clone().into(),
+                            image: asset_server
+                                .load(story::illustration_path(illustration))
+                                .into(),
                             style: Style {
                                 width: Val::Percent(90.),
                                 ..default()
@@ -634,7 +1000,7 @@ fn show_current_node(
                 for decoration in extra.decorations.iter() {
                     parent.spawn((
                         ImageBundle {
-                            image: decoration.clone().into(),
+                            image: asset_server.load(story::decoration_path(decoration)).into(),
                             style: Style {
                                 max_height: Val::Percent(30.),
                                 margin: UiRect::top(Val::Px(20.)),
@@ -656,7 +1022,7 @@ fn show_current_node(
                         });
                 }
             });
-            true
+            Ok(true)
         }
     }
 }
@@ -682,6 +1048,7 @@ fn interact_with_end_button(
             Interaction::Pressed => {
                 log::info!("Pressed end button");
                 graph.reset();
+                save::delete();
                 transition.send_default();
                 erase_everything.send_default();
             }
@@ -697,9 +1064,22 @@ fn interact_with_arrow(
     animations: Res<AnimationAssets>,
     mut event_writer: EventWriter<Transition>,
     mut erase_everything: EventWriter<EraseEverything>,
+    next_pages: Res<NextPages>,
+    mut turn_page: EventWriter<TurnPage>,
+    typewriters: Query<&Typewriter>,
+    graph: Res<BookGraph>,
+    current_page: Res<CurrentPage>,
+    mut play_cues: EventWriter<PlayCues>,
 ) {
+    // While writing, clicking only finishes the text.
+    if typewriters.iter().any(Typewriter::is_writing) {
+        return;
+    }
     for interaction in interaction_query.iter_mut() {
         match *interaction {
+            Interaction::Pressed if next_pages.has_more() => {
+                turn_page.send_default();
+            }
             Interaction::Pressed => {
                 do_flip_page(
                     &audio,
@@ -708,6 +1088,9 @@ fn interact_with_arrow(
                     &animations,
                     &mut event_writer,
                     &mut erase_everything,
+                    &graph,
+                    &current_page,
+                    &mut play_cues,
                 );
             }
             Interaction::Hovered => {}
@@ -716,37 +1099,227 @@ fn interact_with_arrow(
     }
 }
 
+fn interact_with_back_arrow(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>, With<BackArrow>)>,
+    back_arrows: Query<(), With<BackArrow>>,
+    actions: Res<Actions>,
+    mut go_back: EventWriter<GoBack>,
+) {
+    let pressed = interaction_query
+        .iter()
+        .any(|interaction| matches!(interaction, Interaction::Pressed));
+    // The action only works while the arrow is there to be clicked.
+    if pressed || (!back_arrows.is_empty() && actions.just_pressed(Action::PreviousPage)) {
+        go_back.send_default();
+    }
+}
+
+fn go_back_listener(
+    mut events: EventReader<GoBack>,
+    mut graph: ResMut<BookGraph>,
+    mut lifecycle: ResMut<LifecycleManager>,
+    current_page: Res<CurrentPage>,
+    mut erase_everything: EventWriter<EraseEverything>,
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut players: Query<&mut AnimationPlayer>,
+    animations: Res<AnimationAssets>,
+    clips: Res<Assets<AnimationClip>>,
+    mut pending_cues: ResMut<PendingCues>,
+) {
+    for _ in events.read() {
+        if !matches!(
+            lifecycle.0,
+            Lifecycle::Choosing | Lifecycle::Chosen | Lifecycle::SimpleNode | Lifecycle::End
+        ) {
+            continue;
+        }
+        // Undo whatever happened since the page was shown.
+        while graph.get_current_index() != current_page.0 && graph.go_back() {}
+        // After choosing, going back means choosing again.
+        // Otherwise, it means going to the page before this one.
+        if !matches!(lifecycle.0, Lifecycle::Chosen) {
+            graph.go_back();
+        }
+        log::info!("Going back to node {}", graph.get_current_index());
+        // The sounds of the page left behind.
+        pending_cues.clear();
+        erase_everything.send_default();
+        do_flip_page_backwards(&audio, &audio_assets, &mut players, &animations, &clips);
+        lifecycle.0 = Lifecycle::Transitioning;
+    }
+}
+
+/// Replaces the page with an apology and a way back to the menu, instead of crashing.
+fn story_lost_listener(
+    mut commands: Commands,
+    mut events: EventReader<StoryLost>,
+    mut lifecycle: ResMut<LifecycleManager>,
+    erasable_query: Query<Entity, With<Erasable>>,
+    first_page: Query<Entity, With<FirstPage>>,
+    second_page: Query<Entity, With<SecondPage>>,
+    fonts: Fonts,
+    localizer: Localizer,
+    palette: Res<Palette>,
+    mut next_pages: ResMut<NextPages>,
+) {
+    let Some(StoryLost(error)) = events.read().last() else {
+        return;
+    };
+    log::error!("The story got lost: {}", error);
+    lifecycle.0 = Lifecycle::Lost;
+    next_pages.clear();
+    for entity in erasable_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let text_styles = fonts.text_styles();
+    commands
+        .entity(first_page.single())
+        .with_children(|parent| {
+            parent.spawn((
+                get_formatted_text(&localizer.ui("story-lost"), &text_styles, &palette),
+                Erasable,
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    error.to_string(),
+                    TextStyle {
+                        font_size: fonts.size(20.),
+                        ..text_styles.normal.clone()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::top(Val::Px(20.)),
+                    ..default()
+                }),
+                Erasable,
+            ));
+        });
+    commands
+        .entity(second_page.single())
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        background_color: Color::NONE.into(),
+                        style: Style {
+                            padding: UiRect::all(Val::Px(5.)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    BackToMenuButton,
+                    Erasable,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        localizer.ui("back-to-menu"),
+                        text_styles.highlighted.clone(),
+                    ));
+                });
+        });
+}
+
+fn interact_with_back_to_menu_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<BackToMenuButton>),
+    >,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(GameState::Menu);
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Leaves the book for the menu, like the button shown when the story is lost.
+fn open_menu(actions: Res<Actions>, mut next_state: ResMut<NextState<GameState>>) {
+    if actions.just_pressed(Action::Menu) {
+        next_state.set(GameState::Menu);
+    }
+}
+
 fn setup_graph(
     mut commands: Commands,
-    illustrations: Res<Illustrations>,
-    fonts: Res<FontAssets>,
-    ui_textures: Res<UiTextures>,
+    stories: Res<Assets<Story>>,
+    story_assets: Res<StoryAssets>,
+    save: Option<Res<SaveData>>,
 ) {
-    let graph = book_content::get_book_content(&illustrations, &fonts, &ui_textures);
-    // TODO: For testing, remove.
-    // graph.set_current_node(18);
+    let story = &stories
+        .get(&story_assets.book)
+        .expect("The story is loaded before playing")
+        .file;
+    let mut graph = story.to_graph();
+    commands.insert_resource(story.palette().expect("The story was checked when loaded"));
+    if let Some(save) = save {
+        if !save.clone().apply(&mut graph) {
+            log::warn!("The saved game doesn't fit the book anymore, starting over");
+        }
+        commands.remove_resource::<SaveData>();
+    }
     commands.insert_resource(graph);
 }
 
-fn get_formatted_text(text: &str, text_styles: &TextStyles) -> TextBundle {
-    let mut chars_iter = text.chars();
-    let first_letter = chars_iter.next().unwrap();
-    let first_letter_section = iter::once(TextSection {
-        value: first_letter.to_string(),
-        style: text_styles.first_letter.clone(),
+/// The text with its markup, see [`markup`]. Shown as it is if the markup is wrong.
+fn markup_sections(text: &str, text_styles: &TextStyles, palette: &Palette) -> Vec<TextSection> {
+    let spans = markup::parse(text, palette).unwrap_or_else(|error| {
+        log::error!("Couldn't read the markup of `{text}`, {error}");
+        vec![markup::Span {
+            text: text.to_string(),
+            ..default()
+        }]
     });
-    let rest: String = chars_iter.collect();
-    let rest_sections = utils::process_string_asterisks(&rest)
+    spans
         .into_iter()
-        .enumerate()
-        .map(|(index, text)| TextSection {
-            value: text,
-            style: if index % 2 == 0 {
-                text_styles.normal.clone()
-            } else {
+        .map(|span| {
+            let mut style = if span.highlighted {
                 text_styles.highlighted.clone()
-            },
-        });
-    let sections: Vec<TextSection> = first_letter_section.chain(rest_sections).collect();
+            } else {
+                text_styles.normal.clone()
+            };
+            if span.italic {
+                style.font = text_styles.italic.font.clone();
+                if !span.highlighted {
+                    style.color = text_styles.italic.color;
+                }
+            }
+            if let Some(color) = span.color {
+                style.color = color;
+            }
+            TextSection {
+                value: span.text,
+                style,
+            }
+        })
+        .collect()
+}
+
+/// Like [`markup_sections`], with the first letter bigger.
+fn get_formatted_text(text: &str, text_styles: &TextStyles, palette: &Palette) -> TextBundle {
+    let mut sections = markup_sections(text, text_styles, palette);
+    if let Some(first) = sections.first_mut() {
+        let mut chars = first.value.chars();
+        if let Some(first_letter) = chars.next() {
+            let rest = chars.as_str().to_string();
+            first.value = rest;
+            sections.insert(
+                0,
+                TextSection {
+                    value: first_letter.to_string(),
+                    style: text_styles.first_letter.clone(),
+                },
+            );
+        }
+    }
     TextBundle::from_sections(sections)
 }
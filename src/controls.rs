@@ -0,0 +1,192 @@
+//! The controls page of the menu book, a page of the settings where the actions are rebound.
+//! Picking an action waits for the next key, mouse button or gamepad button to bind it, which
+//! replaces its bindings on that device only.
+
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, Actions, Bindings},
+    book::{BUTTON_HOVER_COLOR, BUTTON_NORMAL_COLOR},
+    loading::FontAssets,
+    localization::Localizer,
+    menu::{MenuPage, MENU_BUTTON_RED},
+    settings::{text_button, PageButton, Settings},
+};
+
+#[derive(Component)]
+pub struct RebindButton(Action);
+
+#[derive(Component)]
+pub struct ResetBindingsButton;
+
+/// Each action has its name, what triggers it and the button to change it.
+pub fn spawn_controls_page(
+    parent: &mut ChildBuilder,
+    bindings: &Bindings,
+    rebinding: Option<Action>,
+    fonts: &FontAssets,
+    localizer: &Localizer,
+) {
+    let text_style = |font_size| TextStyle {
+        font: fonts.normal.clone(),
+        font_size,
+        color: Color::BLACK,
+    };
+    parent.spawn(TextBundle::from_section(
+        localizer.ui("settings-controls"),
+        TextStyle {
+            color: MENU_BUTTON_RED,
+            ..text_style(40.)
+        },
+    ));
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for action in Action::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::vertical(Val::Px(4.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(
+                            TextBundle::from_section(localizer.ui(action.key()), text_style(22.))
+                                .with_style(Style {
+                                    width: Val::Percent(30.),
+                                    ..default()
+                                }),
+                        );
+                        let bound: Vec<_> = bindings
+                            .get(action)
+                            .iter()
+                            .map(|binding| binding.name(localizer))
+                            .collect();
+                        parent.spawn(
+                            TextBundle::from_section(bound.join(", "), text_style(16.)).with_style(
+                                Style {
+                                    flex_grow: 1.,
+                                    flex_basis: Val::Px(0.),
+                                    margin: UiRect::horizontal(Val::Px(10.)),
+                                    ..default()
+                                },
+                            ),
+                        );
+                        let text = if rebinding == Some(action) {
+                            localizer.ui("controls-press")
+                        } else {
+                            localizer.ui("controls-change")
+                        };
+                        text_button(parent, text, 20., fonts, RebindButton(action));
+                    });
+            }
+        });
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            text_button(
+                parent,
+                localizer.ui("controls-reset"),
+                30.,
+                fonts,
+                ResetBindingsButton,
+            );
+            text_button(
+                parent,
+                localizer.ui("settings-back"),
+                30.,
+                fonts,
+                PageButton(MenuPage::Settings),
+            );
+        });
+}
+
+pub fn click_rebind_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &RebindButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut menu_page: ResMut<MenuPage>,
+) {
+    for (interaction, RebindButton(action), mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *menu_page = MenuPage::Controls {
+                    rebinding: Some(*action),
+                };
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn click_reset_button(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ResetBindingsButton>),
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                settings.bindings = Bindings::default();
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Binds the first input pressed to the action waiting for one.
+/// Until then, and in the frame it's bound, no action is triggered.
+pub fn bind_pressed_input(
+    mut actions: ResMut<Actions>,
+    mut menu_page: ResMut<MenuPage>,
+    mut settings: ResMut<Settings>,
+) {
+    let MenuPage::Controls {
+        rebinding: Some(action),
+    } = *menu_page
+    else {
+        return;
+    };
+    let binding = actions
+        .inputs()
+        .iter()
+        .copied()
+        .find(|binding| binding.can_be_bound());
+    actions.clear();
+    if let Some(binding) = binding {
+        settings.bindings.rebind(action, binding);
+        *menu_page = MenuPage::Controls { rebinding: None };
+    }
+}
@@ -0,0 +1,45 @@
+//! The sound effects of the story, see [`SoundCue`].
+//! The sounds are loaded along with the story, so they're ready by the time they're played.
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::{book_content::SoundCue, story};
+
+/// Plays the cues, each one after its delay.
+#[derive(Event)]
+pub struct PlayCues(pub Vec<SoundCue>);
+
+/// Cues waiting for their delay to pass.
+#[derive(Resource, Default)]
+pub struct PendingCues(Vec<(Timer, SoundCue)>);
+
+impl PendingCues {
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+pub fn play_cues(
+    mut events: EventReader<PlayCues>,
+    mut pending: ResMut<PendingCues>,
+    time: Res<Time>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+) {
+    for PlayCues(cues) in events.read() {
+        for cue in cues.iter() {
+            let timer = Timer::from_seconds(cue.delay.max(0.), TimerMode::Once);
+            pending.0.push((timer, cue.clone()));
+        }
+    }
+    pending.0.retain_mut(|(timer, cue)| {
+        if !timer.tick(time.delta()).finished() {
+            return true;
+        }
+        audio
+            .play(asset_server.load(story::sound_path(&cue.sound)))
+            .with_volume(cue.volume);
+        false
+    });
+}
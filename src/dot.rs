@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::{
+    book_content::BookGraph,
+    graph::Node,
+    markup,
+    story::{self, StoryError},
+};
+
+/// How many words of the text go in the labels.
+pub const LABEL_WORDS: usize = 6;
+
+/// Graphviz DOT picture of the book in the story file.
+pub fn book_to_dot(path: impl AsRef<Path>) -> Result<String, StoryError> {
+    Ok(to_dot(&story::read_book(path)?))
+}
+
+/// Graphviz DOT picture of the graph.
+/// Routing is resolved by exploring every playthrough, so a choice leading to different nodes
+/// depending on the context has one dashed edge per node.
+pub fn to_dot(graph: &BookGraph) -> String {
+    let exploration = graph.explore();
+    let initial_context = graph.get_initial_context();
+    let context_at = |index| exploration.context_at(index).unwrap_or(initial_context);
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph book {{").unwrap();
+    writeln!(dot, "    node [shape=box];").unwrap();
+    let visited = exploration.visited();
+    for index in graph.get_indices() {
+        let node = graph.get_node(index).unwrap();
+        let (content, look) = match node {
+            Node::Simple {
+                content,
+                next: None,
+                ..
+            } => (content, "peripheries=2"),
+            Node::Simple { content, .. } => (content, "shape=box"),
+            Node::Fork { content, .. } => (content, "style=rounded"),
+        };
+        let text = summary(content.text.get(context_at(index)));
+        let unreachable = if visited.contains(&index) {
+            ""
+        } else {
+            ", color=gray"
+        };
+        writeln!(
+            dot,
+            "    {index} [label=\"{index}: {}\", {look}{unreachable}];",
+            escape(&text)
+        )
+        .unwrap();
+    }
+
+    let edges = exploration.edges();
+    let mut targets_per_choice: BTreeMap<(usize, Option<usize>), usize> = BTreeMap::new();
+    for edge in edges.iter() {
+        *targets_per_choice
+            .entry((edge.from, edge.choice))
+            .or_default() += 1;
+    }
+    for edge in edges.iter() {
+        if graph.get_node(edge.to).is_none() {
+            writeln!(
+                dot,
+                "    {} [label=\"{} (missing)\", color=red];",
+                edge.to, edge.to
+            )
+            .unwrap();
+        }
+        let mut attributes = Vec::new();
+        if let (Some(choice_index), Some(Node::Fork { choices, .. })) =
+            (edge.choice, graph.get_node(edge.from))
+        {
+            let text = summary(choices[choice_index].text.get(context_at(edge.from)));
+            attributes.push(format!("label=\"{}. {}\"", choice_index + 1, escape(&text)));
+        }
+        if targets_per_choice[&(edge.from, edge.choice)] > 1 {
+            attributes.push("style=dashed".to_string());
+        }
+        write!(dot, "    {} -> {}", edge.from, edge.to).unwrap();
+        if !attributes.is_empty() {
+            write!(dot, " [{}]", attributes.join(", ")).unwrap();
+        }
+        writeln!(dot, ";").unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+/// The first words of the text, without the asterisks.
+fn summary(text: &str) -> String {
+    let text = markup::plain_text(text);
+    let mut words = text.split_whitespace();
+    let mut summary = words
+        .by_ref()
+        .take(LABEL_WORDS)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if words.next().is_some() {
+        summary.push_str("...");
+    }
+    summary
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_shows_every_route() {
+        let dot = to_dot(&story::test_book());
+        assert!(dot.starts_with("digraph book {\n"));
+        assert!(dot
+            .contains("    1 [label=\"1: Todavía inmersos en sus delirios, los...\", shape=box];"));
+        assert!(dot.contains("    7 [label=\"7: "));
+        // The first choice of node 3 depends on the choice made in node 2.
+        for to in [4, 8, 10] {
+            assert!(dot.contains(&format!(
+                "    3 -> {to} [label=\"1. Con un misterioso hombre disfrazado de...\", style=dashed];"
+            )));
+        }
+        assert!(dot.contains("    1 -> 2;"));
+        assert!(!dot.contains("missing"));
+    }
+}
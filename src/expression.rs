@@ -0,0 +1,1062 @@
+//! Small language for the logic of the story, so it can be written in the story files
+//! and checked by the tools.
+//!
+//! Expressions read variables and compute a value:
+//! `princesa_guerrera && !salir_cueva`, `visits >= 2`, `if fan_dragones { 8 } else { 10 }`.
+//! Scripts change variables: `fan_dragones = true; visits += 1; mood = 'happy'`.
+//! Texts go between single quotes, so they don't clash with the quotes of the story files.
+
+use std::{collections::BTreeSet, fmt};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+}
+
+impl Value {
+    pub fn get_type(&self) -> Type {
+        match self {
+            Value::Bool(_) => Type::Bool,
+            Value::Int(_) => Type::Int,
+            Value::Text(_) => Type::Text,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Int(value) => write!(f, "{value}"),
+            Value::Text(value) => write!(f, "'{value}'"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Type {
+    Bool,
+    Int,
+    Text,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Bool => write!(f, "a boolean"),
+            Type::Int => write!(f, "an integer"),
+            Type::Text => write!(f, "a text"),
+        }
+    }
+}
+
+/// Where expressions read and write variables, the context of the story.
+pub trait Variables {
+    fn get_variable(&self, name: &str) -> Option<Value>;
+
+    /// Returns whether or not the variable could be set,
+    /// it can't if it doesn't exist or has another type.
+    fn set_variable(&mut self, name: &str, value: Value) -> bool;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    Not,
+    Negate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Add,
+    Subtract,
+}
+
+impl BinaryOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            BinaryOp::And => "&&",
+            BinaryOp::Or => "||",
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessOrEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterOrEqual => ">=",
+            BinaryOp::Add => "+",
+            BinaryOp::Subtract => "-",
+        }
+    }
+
+    /// Higher binds tighter.
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::Less
+            | BinaryOp::LessOrEqual
+            | BinaryOp::Greater
+            | BinaryOp::GreaterOrEqual => 3,
+            BinaryOp::Add | BinaryOp::Subtract => 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression {
+    Literal(Value),
+    Variable(String),
+    Unary(UnaryOp, Box<Expression>),
+    Binary(BinaryOp, Box<Expression>, Box<Expression>),
+    If {
+        condition: Box<Expression>,
+        then: Box<Expression>,
+        otherwise: Box<Expression>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    /// `name = value`, `name += value` and `name -= value`, the last two as [`BinaryOp`]s.
+    Assign {
+        name: String,
+        op: Option<BinaryOp>,
+        value: Expression,
+    },
+    If {
+        condition: Expression,
+        then: Script,
+        otherwise: Script,
+    },
+}
+
+/// Statements run one after the other.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Script(pub Vec<Statement>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// In characters from the start of the source.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at character {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    UnknownVariable(String),
+    WrongType {
+        expected: Type,
+        found: Type,
+        expression: String,
+    },
+    CantAssign {
+        name: String,
+        value: Value,
+    },
+    Overflow(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::UnknownVariable(name) => write!(f, "there's no variable `{name}`"),
+            EvalError::WrongType {
+                expected,
+                found,
+                expression,
+            } => write!(f, "`{expression}` is {found} but {expected} was expected"),
+            EvalError::CantAssign { name, value } => {
+                write!(f, "`{value}` can't be assigned to `{name}`")
+            }
+            EvalError::Overflow(expression) => write!(f, "`{expression}` overflows"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(source)?;
+        let expression = parser.expression()?;
+        parser.expect_end()?;
+        Ok(expression)
+    }
+
+    pub fn eval(&self, variables: &impl Variables) -> Result<Value, EvalError> {
+        match self {
+            Expression::Literal(value) => Ok(value.clone()),
+            Expression::Variable(name) => variables
+                .get_variable(name)
+                .ok_or_else(|| EvalError::UnknownVariable(name.clone())),
+            Expression::Unary(UnaryOp::Not, operand) => {
+                Ok(Value::Bool(!operand.eval_bool(variables)?))
+            }
+            Expression::Unary(UnaryOp::Negate, operand) => operand
+                .eval_int(variables)?
+                .checked_neg()
+                .map(Value::Int)
+                .ok_or_else(|| EvalError::Overflow(self.to_string())),
+            // Short-circuits, so the right side can rely on the left one.
+            Expression::Binary(BinaryOp::And, left, right) => Ok(Value::Bool(
+                left.eval_bool(variables)? && right.eval_bool(variables)?,
+            )),
+            Expression::Binary(BinaryOp::Or, left, right) => Ok(Value::Bool(
+                left.eval_bool(variables)? || right.eval_bool(variables)?,
+            )),
+            Expression::Binary(op @ (BinaryOp::Equal | BinaryOp::NotEqual), left, right) => {
+                let left_value = left.eval(variables)?;
+                let right_value = right.eval(variables)?;
+                if left_value.get_type() != right_value.get_type() {
+                    return Err(EvalError::WrongType {
+                        expected: left_value.get_type(),
+                        found: right_value.get_type(),
+                        expression: right.to_string(),
+                    });
+                }
+                Ok(Value::Bool(
+                    (left_value == right_value) == (*op == BinaryOp::Equal),
+                ))
+            }
+            Expression::Binary(op, left, right) => {
+                let left = left.eval_int(variables)?;
+                let right = right.eval_int(variables)?;
+                let overflow = || EvalError::Overflow(self.to_string());
+                Ok(match op {
+                    BinaryOp::Less => Value::Bool(left < right),
+                    BinaryOp::LessOrEqual => Value::Bool(left <= right),
+                    BinaryOp::Greater => Value::Bool(left > right),
+                    BinaryOp::GreaterOrEqual => Value::Bool(left >= right),
+                    BinaryOp::Add => Value::Int(left.checked_add(right).ok_or_else(overflow)?),
+                    BinaryOp::Subtract => Value::Int(left.checked_sub(right).ok_or_else(overflow)?),
+                    _ => unreachable!("Boolean operators are handled above"),
+                })
+            }
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                if condition.eval_bool(variables)? {
+                    then.eval(variables)
+                } else {
+                    otherwise.eval(variables)
+                }
+            }
+        }
+    }
+
+    pub fn eval_bool(&self, variables: &impl Variables) -> Result<bool, EvalError> {
+        match self.eval(variables)? {
+            Value::Bool(value) => Ok(value),
+            value => Err(self.wrong_type(Type::Bool, value.get_type())),
+        }
+    }
+
+    pub fn eval_int(&self, variables: &impl Variables) -> Result<i64, EvalError> {
+        match self.eval(variables)? {
+            Value::Int(value) => Ok(value),
+            value => Err(self.wrong_type(Type::Int, value.get_type())),
+        }
+    }
+
+    fn wrong_type(&self, expected: Type, found: Type) -> EvalError {
+        EvalError::WrongType {
+            expected,
+            found,
+            expression: self.to_string(),
+        }
+    }
+
+    /// Works out the type without running it, so mistakes show up before anyone reads that page.
+    /// `types` gives the type of each variable.
+    pub fn check(&self, types: &impl Fn(&str) -> Option<Type>) -> Result<Type, EvalError> {
+        let expect = |expression: &Expression, expected: Type| {
+            let found = expression.check(types)?;
+            if found == expected {
+                Ok(())
+            } else {
+                Err(expression.wrong_type(expected, found))
+            }
+        };
+        match self {
+            Expression::Literal(value) => Ok(value.get_type()),
+            Expression::Variable(name) => {
+                types(name).ok_or_else(|| EvalError::UnknownVariable(name.clone()))
+            }
+            Expression::Unary(UnaryOp::Not, operand) => {
+                expect(operand, Type::Bool).map(|_| Type::Bool)
+            }
+            Expression::Unary(UnaryOp::Negate, operand) => {
+                expect(operand, Type::Int).map(|_| Type::Int)
+            }
+            Expression::Binary(op, left, right) => match op {
+                BinaryOp::And | BinaryOp::Or => {
+                    expect(left, Type::Bool)?;
+                    expect(right, Type::Bool)?;
+                    Ok(Type::Bool)
+                }
+                BinaryOp::Equal | BinaryOp::NotEqual => {
+                    expect(right, left.check(types)?)?;
+                    Ok(Type::Bool)
+                }
+                BinaryOp::Add | BinaryOp::Subtract => {
+                    expect(left, Type::Int)?;
+                    expect(right, Type::Int)?;
+                    Ok(Type::Int)
+                }
+                _ => {
+                    expect(left, Type::Int)?;
+                    expect(right, Type::Int)?;
+                    Ok(Type::Bool)
+                }
+            },
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                expect(condition, Type::Bool)?;
+                let then_type = then.check(types)?;
+                expect(otherwise, then_type)?;
+                Ok(then_type)
+            }
+        }
+    }
+
+    /// Every variable the expression reads.
+    pub fn variables(&self) -> BTreeSet<&str> {
+        let mut variables = BTreeSet::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut BTreeSet<&'a str>) {
+        match self {
+            Expression::Literal(_) => {}
+            Expression::Variable(name) => {
+                variables.insert(name);
+            }
+            Expression::Unary(_, operand) => operand.collect_variables(variables),
+            Expression::Binary(_, left, right) => {
+                left.collect_variables(variables);
+                right.collect_variables(variables);
+            }
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                condition.collect_variables(variables);
+                then.collect_variables(variables);
+                otherwise.collect_variables(variables);
+            }
+        }
+    }
+
+    /// Every value the expression can end up with, following both sides of each `if`.
+    /// Useful to know every node a choice can lead to.
+    pub fn outcomes(&self) -> Vec<&Expression> {
+        match self {
+            Expression::If {
+                then, otherwise, ..
+            } => {
+                let mut outcomes = then.outcomes();
+                outcomes.extend(otherwise.outcomes());
+                outcomes
+            }
+            _ => vec![self],
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::If { .. } => 0,
+            Expression::Binary(op, _, _) => op.precedence(),
+            _ => u8::MAX,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, precedence: u8) -> fmt::Result {
+        if self.precedence() < precedence {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Literal(value) => write!(f, "{value}"),
+            Expression::Variable(name) => write!(f, "{name}"),
+            Expression::Unary(op, operand) => {
+                write!(f, "{}", if *op == UnaryOp::Not { "!" } else { "-" })?;
+                operand.fmt_operand(f, u8::MAX)
+            }
+            Expression::Binary(op, left, right) => {
+                left.fmt_operand(f, op.precedence())?;
+                write!(f, " {} ", op.symbol())?;
+                // Operators are left-associative, so the right side needs parentheses sooner.
+                right.fmt_operand(f, op.precedence() + 1)
+            }
+            Expression::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                write!(f, "if {condition} {{ {then} }} else ")?;
+                match otherwise.as_ref() {
+                    Expression::If { .. } => write!(f, "{otherwise}"),
+                    _ => write!(f, "{{ {otherwise} }}"),
+                }
+            }
+        }
+    }
+}
+
+impl Script {
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser::new(source)?;
+        let script = parser.script()?;
+        parser.expect_end()?;
+        Ok(script)
+    }
+
+    pub fn run(&self, variables: &mut impl Variables) -> Result<(), EvalError> {
+        for statement in self.0.iter() {
+            match statement {
+                Statement::Assign { name, op, value } => {
+                    let new_value = match (op, value.eval(variables)?) {
+                        (None, new_value) => new_value,
+                        (Some(op), Value::Int(change)) => {
+                            let current = Expression::Variable(name.clone()).eval_int(variables)?;
+                            let result = match op {
+                                BinaryOp::Add => current.checked_add(change),
+                                _ => current.checked_sub(change),
+                            };
+                            Value::Int(
+                                result.ok_or_else(|| EvalError::Overflow(statement.to_string()))?,
+                            )
+                        }
+                        (Some(_), change) => {
+                            return Err(value.wrong_type(Type::Int, change.get_type()))
+                        }
+                    };
+                    if !variables.set_variable(name, new_value.clone()) {
+                        return Err(match variables.get_variable(name) {
+                            None => EvalError::UnknownVariable(name.clone()),
+                            Some(_) => EvalError::CantAssign {
+                                name: name.clone(),
+                                value: new_value,
+                            },
+                        });
+                    }
+                }
+                Statement::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    if condition.eval_bool(variables)? {
+                        then.run(variables)?;
+                    } else {
+                        otherwise.run(variables)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the types of everything in the script without running it.
+    pub fn check(&self, types: &impl Fn(&str) -> Option<Type>) -> Result<(), EvalError> {
+        for statement in self.0.iter() {
+            match statement {
+                Statement::Assign { name, op, value } => {
+                    let variable_type =
+                        types(name).ok_or_else(|| EvalError::UnknownVariable(name.clone()))?;
+                    let value_type = value.check(types)?;
+                    let expected = if op.is_some() {
+                        Type::Int
+                    } else {
+                        variable_type
+                    };
+                    if variable_type != expected || value_type != expected {
+                        return Err(EvalError::WrongType {
+                            expected: variable_type,
+                            found: value_type,
+                            expression: statement.to_string(),
+                        });
+                    }
+                }
+                Statement::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    let condition_type = condition.check(types)?;
+                    if condition_type != Type::Bool {
+                        return Err(condition.wrong_type(Type::Bool, condition_type));
+                    }
+                    then.check(types)?;
+                    otherwise.check(types)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every variable the script reads.
+    pub fn reads(&self) -> BTreeSet<&str> {
+        let mut reads = BTreeSet::new();
+        for statement in self.0.iter() {
+            match statement {
+                Statement::Assign { name, op, value } => {
+                    if op.is_some() {
+                        reads.insert(name.as_str());
+                    }
+                    value.collect_variables(&mut reads);
+                }
+                Statement::If {
+                    condition,
+                    then,
+                    otherwise,
+                } => {
+                    condition.collect_variables(&mut reads);
+                    reads.extend(then.reads());
+                    reads.extend(otherwise.reads());
+                }
+            }
+        }
+        reads
+    }
+
+    /// Every variable the script can change.
+    pub fn writes(&self) -> BTreeSet<&str> {
+        let mut writes = BTreeSet::new();
+        for statement in self.0.iter() {
+            match statement {
+                Statement::Assign { name, .. } => {
+                    writes.insert(name.as_str());
+                }
+                Statement::If {
+                    then, otherwise, ..
+                } => {
+                    writes.extend(then.writes());
+                    writes.extend(otherwise.writes());
+                }
+            }
+        }
+        writes
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Statement::Assign { name, op, value } => {
+                let symbol = match op {
+                    None => "=",
+                    Some(BinaryOp::Add) => "+=",
+                    Some(_) => "-=",
+                };
+                write!(f, "{name} {symbol} {value}")
+            }
+            Statement::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                write!(f, "if {condition} {{ {then} }}")?;
+                match otherwise.0.as_slice() {
+                    [] => Ok(()),
+                    [statement @ Statement::If { .. }] => write!(f, " else {statement}"),
+                    _ => write!(f, " else {{ {otherwise} }}"),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, statement) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{statement}")?;
+        }
+        Ok(())
+    }
+}
+
+// Both are written as strings in the story files.
+impl Serialize for Expression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Expression::parse(&source).map_err(|error| de::Error::custom(format!("`{source}` {error}")))
+    }
+}
+
+impl Serialize for Script {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Script {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Script::parse(&source).map_err(|error| de::Error::custom(format!("`{source}` {error}")))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Int(i64),
+    Text(String),
+    Identifier(String),
+    Symbol(&'static str),
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Int(value) => write!(f, "`{value}`"),
+            Token::Text(value) => write!(f, "`'{value}'`"),
+            Token::Identifier(name) => write!(f, "`{name}`"),
+            Token::Symbol(symbol) => write!(f, "`{symbol}`"),
+            Token::End => write!(f, "the end"),
+        }
+    }
+}
+
+/// Longer symbols first, so `<=` isn't read as `<` and `=`.
+const SYMBOLS: [&str; 19] = [
+    "&&", "||", "==", "!=", "<=", ">=", "+=", "-=", "<", ">", "=", "!", "+", "-", "(", ")", "{",
+    "}", ";",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let start = position;
+        let c = chars[position];
+        if c.is_whitespace() {
+            position += 1;
+        } else if c.is_ascii_digit() {
+            while position < chars.len() && chars[position].is_ascii_digit() {
+                position += 1;
+            }
+            let digits: String = chars[start..position].iter().collect();
+            let value = digits.parse().map_err(|_| ParseError {
+                position: start,
+                message: format!("`{digits}` is too big"),
+            })?;
+            tokens.push((start, Token::Int(value)));
+        } else if c == '\'' {
+            let Some(length) = chars[start + 1..].iter().position(|c| *c == '\'') else {
+                return Err(ParseError {
+                    position: start,
+                    message: "the text never ends, it's missing a `'`".to_string(),
+                });
+            };
+            position = start + length + 2;
+            let text = chars[start + 1..position - 1].iter().collect();
+            tokens.push((start, Token::Text(text)));
+        } else if c.is_alphabetic() || c == '_' {
+            while position < chars.len()
+                && (chars[position].is_alphanumeric() || chars[position] == '_')
+            {
+                position += 1;
+            }
+            tokens.push((
+                start,
+                Token::Identifier(chars[start..position].iter().collect()),
+            ));
+        } else {
+            let rest: String = chars[position..chars.len().min(position + 2)]
+                .iter()
+                .collect();
+            let Some(symbol) = SYMBOLS.into_iter().find(|symbol| rest.starts_with(symbol)) else {
+                return Err(ParseError {
+                    position,
+                    message: format!("unexpected `{c}`"),
+                });
+            };
+            position += symbol.len();
+            tokens.push((start, Token::Symbol(symbol)));
+        }
+    }
+    tokens.push((chars.len(), Token::End));
+    Ok(tokens)
+}
+
+const KEYWORDS: [&str; 4] = ["if", "else", "true", "false"];
+
+/// Recursive descent, one function per level of precedence.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            next: 0,
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.next].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.next].1.clone();
+        if token != Token::End {
+            self.next += 1;
+        }
+        token
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        let (position, token) = &self.tokens[self.next];
+        ParseError {
+            position: *position,
+            message: format!("expected {expected} but found {token}"),
+        }
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        if self.is_symbol(symbol) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Token::Symbol(found) if *found == symbol)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Identifier(name) if name == keyword)
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), ParseError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("`{symbol}`")))
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), ParseError> {
+        if *self.peek() == Token::End {
+            Ok(())
+        } else {
+            Err(self.error("the end"))
+        }
+    }
+
+    fn script(&mut self) -> Result<Script, ParseError> {
+        let mut statements = Vec::new();
+        while *self.peek() != Token::End && !self.is_symbol("}") {
+            statements.push(self.statement()?);
+            if !self.eat_symbol(";") {
+                break;
+            }
+        }
+        Ok(Script(statements))
+    }
+
+    fn statement(&mut self) -> Result<Statement, ParseError> {
+        if self.is_keyword("if") {
+            self.advance();
+            let condition = self.expression()?;
+            let then = self.script_block()?;
+            let otherwise = if self.is_keyword("else") {
+                self.advance();
+                if self.is_keyword("if") {
+                    Script(vec![self.statement()?])
+                } else {
+                    self.script_block()?
+                }
+            } else {
+                Script::default()
+            };
+            return Ok(Statement::If {
+                condition,
+                then,
+                otherwise,
+            });
+        }
+        let name = self.variable_name()?;
+        let op = if self.eat_symbol("=") {
+            None
+        } else if self.eat_symbol("+=") {
+            Some(BinaryOp::Add)
+        } else if self.eat_symbol("-=") {
+            Some(BinaryOp::Subtract)
+        } else {
+            return Err(self.error("`=`, `+=` or `-=`"));
+        };
+        let value = self.expression()?;
+        Ok(Statement::Assign { name, op, value })
+    }
+
+    fn script_block(&mut self) -> Result<Script, ParseError> {
+        self.expect_symbol("{")?;
+        let script = self.script()?;
+        self.expect_symbol("}")?;
+        Ok(script)
+    }
+
+    fn variable_name(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Token::Identifier(name) if !KEYWORDS.contains(&name.as_str()) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error("a variable")),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expression, ParseError> {
+        if self.is_keyword("if") {
+            self.advance();
+            let condition = self.expression()?;
+            let then = self.expression_block()?;
+            if !self.is_keyword("else") {
+                return Err(self.error("`else`"));
+            }
+            self.advance();
+            let otherwise = if self.is_keyword("if") {
+                self.expression()?
+            } else {
+                self.expression_block()?
+            };
+            return Ok(Expression::If {
+                condition: Box::new(condition),
+                then: Box::new(then),
+                otherwise: Box::new(otherwise),
+            });
+        }
+        self.binary(1)
+    }
+
+    fn expression_block(&mut self) -> Result<Expression, ParseError> {
+        self.expect_symbol("{")?;
+        let expression = self.expression()?;
+        self.expect_symbol("}")?;
+        Ok(expression)
+    }
+
+    fn binary(&mut self, precedence: u8) -> Result<Expression, ParseError> {
+        if precedence > 4 {
+            return self.unary();
+        }
+        let mut left = self.binary(precedence + 1)?;
+        while let Some(op) = self.binary_op(precedence) {
+            self.advance();
+            let right = self.binary(precedence + 1)?;
+            left = Expression::Binary(op, Box::new(left), Box::new(right));
+            // Comparisons don't chain, `a < b < c` is a mistake.
+            if precedence == 3 {
+                break;
+            }
+        }
+        Ok(left)
+    }
+
+    /// The operator coming next, if it has that precedence.
+    fn binary_op(&self, precedence: u8) -> Option<BinaryOp> {
+        let Token::Symbol(symbol) = self.peek() else {
+            return None;
+        };
+        let op = match *symbol {
+            "||" => BinaryOp::Or,
+            "&&" => BinaryOp::And,
+            "==" => BinaryOp::Equal,
+            "!=" => BinaryOp::NotEqual,
+            "<" => BinaryOp::Less,
+            "<=" => BinaryOp::LessOrEqual,
+            ">" => BinaryOp::Greater,
+            ">=" => BinaryOp::GreaterOrEqual,
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Subtract,
+            _ => return None,
+        };
+        (op.precedence() == precedence).then_some(op)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ParseError> {
+        if self.eat_symbol("!") {
+            return Ok(Expression::Unary(UnaryOp::Not, Box::new(self.unary()?)));
+        }
+        if self.eat_symbol("-") {
+            return Ok(Expression::Unary(UnaryOp::Negate, Box::new(self.unary()?)));
+        }
+        match self.peek().clone() {
+            Token::Int(value) => {
+                self.advance();
+                Ok(Expression::Literal(Value::Int(value)))
+            }
+            Token::Text(value) => {
+                self.advance();
+                Ok(Expression::Literal(Value::Text(value)))
+            }
+            Token::Identifier(name) if name == "true" || name == "false" => {
+                self.advance();
+                Ok(Expression::Literal(Value::Bool(name == "true")))
+            }
+            Token::Identifier(name) if name == "if" => self.expression(),
+            Token::Identifier(_) => Ok(Expression::Variable(self.variable_name()?)),
+            Token::Symbol("(") => {
+                self.advance();
+                let expression = self.expression()?;
+                self.expect_symbol(")")?;
+                Ok(expression)
+            }
+            _ => Err(self.error("a value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    impl Variables for HashMap<&str, Value> {
+        fn get_variable(&self, name: &str) -> Option<Value> {
+            self.get(name).cloned()
+        }
+
+        fn set_variable(&mut self, name: &str, value: Value) -> bool {
+            match self.get_mut(name) {
+                Some(old) if old.get_type() == value.get_type() => {
+                    *old = value;
+                    true
+                }
+                _ => false,
+            }
+        }
+    }
+
+    fn variables() -> HashMap<&'static str, Value> {
+        HashMap::from([
+            ("guerrera", Value::Bool(true)),
+            ("fan", Value::Bool(false)),
+            ("visits", Value::Int(2)),
+            ("mood", Value::Text("grumpy".to_string())),
+        ])
+    }
+
+    #[test]
+    fn expressions_are_evaluated() {
+        let variables = variables();
+        let eval = |source| Expression::parse(source).unwrap().eval(&variables).unwrap();
+        assert_eq!(eval("guerrera && !fan"), Value::Bool(true));
+        assert_eq!(eval("fan || visits >= 2 && visits < 3"), Value::Bool(true));
+        assert_eq!(eval("visits + 1 - -2 == 5"), Value::Bool(true));
+        assert_eq!(eval("guerrera != (fan == false)"), Value::Bool(false));
+        assert_eq!(
+            eval("if fan { 8 } else if guerrera { 4 } else { 10 }"),
+            Value::Int(4)
+        );
+        assert_eq!(eval("mood != 'happy'"), Value::Bool(true));
+    }
+
+    #[test]
+    fn scripts_change_variables() {
+        let mut variables = variables();
+        let script = Script::parse("fan = true; visits += 3; if fan { guerrera = false }").unwrap();
+        assert_eq!(
+            script.writes(),
+            BTreeSet::from(["fan", "guerrera", "visits"])
+        );
+        script.run(&mut variables).unwrap();
+        assert_eq!(variables["fan"], Value::Bool(true));
+        assert_eq!(variables["guerrera"], Value::Bool(false));
+        assert_eq!(variables["visits"], Value::Int(5));
+
+        let error = Script::parse("fan = 1").unwrap().run(&mut variables);
+        assert!(matches!(error, Err(EvalError::CantAssign { .. })));
+    }
+
+    #[test]
+    fn mistakes_are_reported() {
+        let error = Expression::parse("guerrera && (fan ||").unwrap_err();
+        assert_eq!(error.position, 19);
+        assert_eq!(
+            Expression::parse("visits < 2 < 3").unwrap_err().position,
+            11
+        );
+        assert_eq!(Expression::parse("if fan { 1 }").unwrap_err().position, 12);
+        assert_eq!(Script::parse("fan == true").unwrap_err().position, 4);
+
+        let variables = variables();
+        let types = |name: &str| variables.get(name).map(Value::get_type);
+        let check = |source| Expression::parse(source).unwrap().check(&types);
+        assert_eq!(check("if fan { visits } else { 0 }"), Ok(Type::Int));
+        assert!(matches!(check("fan + 1"), Err(EvalError::WrongType { .. })));
+        assert!(matches!(
+            check("if fan { 1 } else { true }"),
+            Err(EvalError::WrongType { .. })
+        ));
+        assert_eq!(
+            check("volar"),
+            Err(EvalError::UnknownVariable("volar".to_string()))
+        );
+    }
+
+    #[test]
+    fn expressions_print_back_as_source() {
+        for source in [
+            "a && (b || !c)",
+            "a - (b - c) == -1",
+            "if a { 1 } else if b { 2 } else { 3 }",
+        ] {
+            assert_eq!(Expression::parse(source).unwrap().to_string(), source);
+        }
+        let source = "a = true; b += 1; if a { c = 'yes' } else { d = 1 - 2 }";
+        assert_eq!(Script::parse(source).unwrap().to_string(), source);
+    }
+}
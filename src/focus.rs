@@ -0,0 +1,155 @@
+//! Which of the choices of a fork is focused, so it can be picked without the mouse.
+//! Hovering a choice focuses it too, so only one of them is ever highlighted,
+//! whether the mouse or the [`Action`]s are used.
+
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, Actions},
+    book::{ChoicesOption, BUTTON_HOVER_COLOR, BUTTON_NORMAL_COLOR},
+    pagination::NextPages,
+    typewriter::Typewriter,
+};
+
+/// The position of the focused choice, if any.
+#[derive(Resource, Default)]
+pub struct ChoiceFocus(pub Option<usize>);
+
+/// Picks the choice in the position, like clicking it does.
+#[derive(Event)]
+pub struct ConfirmChoice(pub usize);
+
+/// The next choice that can be picked, going around at the ends.
+fn next_focus(positions: &[usize], focus: Option<usize>, forward: bool) -> Option<usize> {
+    let current = focus.and_then(|focus| positions.iter().position(|&p| p == focus));
+    let next = match (current, forward) {
+        (None, true) => 0,
+        (None, false) => positions.len().checked_sub(1)?,
+        (Some(current), true) => (current + 1) % positions.len(),
+        (Some(current), false) => (current + positions.len() - 1) % positions.len(),
+    };
+    positions.get(next).copied()
+}
+
+/// The positions of the choices that can be picked right now.
+/// They're hidden along with the rest of the second page while there's text left to read,
+/// and new ones count as hidden until their visibility is worked out.
+fn shown_positions<'a>(
+    choices: impl Iterator<Item = (&'a ChoicesOption, &'a InheritedVisibility)>,
+) -> Vec<usize> {
+    let mut positions: Vec<usize> = choices
+        .filter(|(choice, visibility)| !choice.locked && visibility.get())
+        .map(|(choice, _)| choice.position)
+        .collect();
+    positions.sort_unstable();
+    positions
+}
+
+/// New choices start without focus.
+pub fn move_focus(
+    actions: Res<Actions>,
+    choices: Query<(&ChoicesOption, &InheritedVisibility)>,
+    new_choices: Query<(), Added<ChoicesOption>>,
+    mut focus: ResMut<ChoiceFocus>,
+) {
+    if !new_choices.is_empty() {
+        focus.0 = None;
+    }
+    let forward = actions.just_pressed(Action::FocusNext);
+    let backward = actions.just_pressed(Action::FocusPrev);
+    if forward == backward {
+        return;
+    }
+    let positions = shown_positions(choices.iter());
+    if !positions.is_empty() {
+        focus.0 = next_focus(&positions, focus.0, forward);
+    }
+}
+
+pub fn hover_choices(
+    interaction_query: Query<(&Interaction, &ChoicesOption), Changed<Interaction>>,
+    mut focus: ResMut<ChoiceFocus>,
+) {
+    for (interaction, choice) in interaction_query.iter() {
+        if choice.locked {
+            continue;
+        }
+        match *interaction {
+            Interaction::Hovered | Interaction::Pressed => {
+                focus.0 = Some(choice.position);
+            }
+            Interaction::None => {
+                if focus.0 == Some(choice.position) {
+                    focus.0 = None;
+                }
+            }
+        }
+    }
+}
+
+pub fn highlight_focused_choice(
+    focus: Res<ChoiceFocus>,
+    mut choices: Query<(&ChoicesOption, &mut BackgroundColor)>,
+    new_choices: Query<(), Added<ChoicesOption>>,
+) {
+    if !focus.is_changed() && new_choices.is_empty() {
+        return;
+    }
+    for (choice, mut background_color) in choices.iter_mut() {
+        *background_color = if focus.0 == Some(choice.position) {
+            BUTTON_HOVER_COLOR.into()
+        } else {
+            BUTTON_NORMAL_COLOR.into()
+        };
+    }
+}
+
+/// Confirming picks the focused choice, unless the same input turns to more text to read.
+/// [`Action::Choose1`] and the others pick the choices in their positions directly.
+/// While writing, they only finish the text, and the choices are only shown the frame after.
+pub fn confirm_focused_choice(
+    actions: Res<Actions>,
+    focus: Res<ChoiceFocus>,
+    choices: Query<(&ChoicesOption, &InheritedVisibility)>,
+    typewriters: Query<&Typewriter>,
+    next_pages: Res<NextPages>,
+    mut confirm_choice: EventWriter<ConfirmChoice>,
+) {
+    let positions = shown_positions(choices.iter());
+    if positions.is_empty() || typewriters.iter().any(Typewriter::is_writing) {
+        return;
+    }
+    let confirmed = actions.just_pressed(Action::Confirm)
+        && !(actions.just_pressed(Action::NextPage) && next_pages.has_more());
+    let position = if confirmed {
+        focus.0
+    } else {
+        Action::CHOOSE
+            .iter()
+            .position(|&choose| actions.just_pressed(choose))
+    };
+    let Some(position) = position else {
+        return;
+    };
+    if positions.contains(&position) {
+        confirm_choice.send(ConfirmChoice(position));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_goes_around_the_choices() {
+        let positions = [0, 2, 3];
+        assert_eq!(next_focus(&positions, None, true), Some(0));
+        assert_eq!(next_focus(&positions, None, false), Some(3));
+        assert_eq!(next_focus(&positions, Some(0), true), Some(2));
+        assert_eq!(next_focus(&positions, Some(3), true), Some(0));
+        assert_eq!(next_focus(&positions, Some(0), false), Some(3));
+        // The focused choice isn't there anymore.
+        assert_eq!(next_focus(&positions, Some(1), true), Some(0));
+        assert_eq!(next_focus(&[], None, true), None);
+    }
+}
@@ -0,0 +1,614 @@
+//! The story as a classic gamebook, to be printed: numbered sections in no particular order,
+//! where every choice says which section to turn to.
+//!
+//! Where a choice leads can depend on the choices made before it, so a node is written once for
+//! every context it can be read with, as found by [`crate::graph::Graph::explore`].
+//! Choices that are followed by a text or an illustration get a section of their own, like the
+//! page the game shows after choosing.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use fluent_bundle::FluentResource;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    book_content::{BookContext, BookGraph, Text},
+    graph::{Node, PlaythroughEnd},
+    localization::{self, Locales, LocalizationError},
+    markup::{self, Palette, Span},
+    menu::Language,
+    story::{self, StoryError, StoryFile},
+};
+
+pub const TITLE: &str = "Sant Jordi";
+/// The sections of a story are always shuffled in the same way,
+/// so every print of it has the same numbers. It's the 23rd of April.
+pub const SEED: u64 = 23;
+
+#[derive(Debug)]
+pub enum GamebookError {
+    Story(StoryError),
+    Localization(LocalizationError),
+    UnknownLanguage(String),
+}
+
+impl fmt::Display for GamebookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GamebookError::Story(error) => write!(f, "{error}"),
+            GamebookError::Localization(error) => write!(f, "{error}"),
+            GamebookError::UnknownLanguage(code) => write!(f, "there's no `{code}` language"),
+        }
+    }
+}
+
+impl std::error::Error for GamebookError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub number: usize,
+    /// The node it comes from, none for the section of the story getting lost.
+    pub node: Option<usize>,
+    /// With their markup, in the order they're read.
+    pub texts: Vec<String>,
+    pub illustration: Option<PathBuf>,
+    pub exits: Exits,
+}
+
+/// How a section goes on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Exits {
+    /// Like at a fork without any choice to take, which loses the story.
+    TurnTo(usize),
+    Choices(Vec<Choice>),
+    /// An ending, or the story got lost.
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Choice {
+    pub text: String,
+    /// The section it leads to, none if it's locked.
+    pub section: Option<usize>,
+    /// Why it's locked, if it is.
+    pub locked_reason: Option<String>,
+}
+
+pub struct Gamebook {
+    /// In order, the first one is where the story starts.
+    pub sections: Vec<Section>,
+    language: Language,
+    palette: Palette,
+    turn_to: String,
+    the_end: String,
+}
+
+/// The nodes along with every context they're read with.
+struct Unfolded {
+    /// The first one is the start of the story.
+    states: Vec<(usize, BookContext)>,
+    /// The state each choice leads to, or where a simple node goes on, by state.
+    /// None if the story gets lost on the way.
+    routes: HashMap<(usize, Option<usize>), Option<usize>>,
+    /// Whether the story can get lost.
+    lost: bool,
+}
+
+impl Gamebook {
+    /// In the given language, with the illustrations in the `images` folder.
+    pub fn new(
+        story: &StoryFile,
+        translations: impl IntoIterator<Item = (Language, FluentResource)>,
+        language: Language,
+        images: &Path,
+    ) -> Result<Self, GamebookError> {
+        let story_texts = localization::story_ftl(story).map_err(GamebookError::Localization)?;
+        let translations = translations
+            .into_iter()
+            .map(|(language, resource)| (language, Arc::new(resource)));
+        let locales = Locales::new(Arc::new(story_texts), translations);
+        let palette = story.palette().map_err(GamebookError::Story)?;
+        let graph = story.to_graph();
+        let Unfolded {
+            states,
+            routes,
+            lost,
+        } = unfold(&graph);
+
+        let text = |key: String, text: &Text, context: &BookContext| {
+            locales.text(language, &key, text, context).into_owned()
+        };
+        let image = |name: &String| images.join(story::illustration_path(name));
+        let texts = |texts: Vec<String>| -> Vec<String> {
+            texts.into_iter().filter(|text| !text.is_empty()).collect()
+        };
+
+        // Numbered by position for now: the states, the story getting lost, then the choices.
+        let lost_section = states.len();
+        let first_choice = states.len() + usize::from(lost);
+        let mut sections = Vec::new();
+        let mut choice_sections = Vec::new();
+        for (state, (index, context)) in states.iter().enumerate() {
+            let index = *index;
+            let route = |choice| match routes.get(&(state, choice)) {
+                Some(Some(to)) => *to,
+                _ => lost_section,
+            };
+            let node = graph.get_node(index).expect("Explored nodes exist");
+            let section = match node {
+                Node::Simple {
+                    content,
+                    extra,
+                    next,
+                } => Section {
+                    number: state,
+                    node: Some(index),
+                    texts: texts(vec![
+                        text(
+                            localization::node_key(index, "text"),
+                            &content.text,
+                            context,
+                        ),
+                        text(
+                            localization::node_key(index, "additional-text"),
+                            &extra.additional_text,
+                            context,
+                        ),
+                    ]),
+                    illustration: extra.illustration.as_ref().map(image),
+                    exits: match next {
+                        Some(_) => Exits::TurnTo(route(None)),
+                        None => Exits::End,
+                    },
+                },
+                Node::Fork { content, choices } => {
+                    let mut shown = Vec::new();
+                    for (position, choice) in choices.iter().enumerate() {
+                        if !choice.is_shown(context) {
+                            continue;
+                        }
+                        let choice_text = text(
+                            localization::choice_key(index, position, "text"),
+                            &choice.text,
+                            context,
+                        );
+                        if let Some(reason) = choice.locked_reason(context) {
+                            shown.push(Choice {
+                                text: choice_text,
+                                section: None,
+                                locked_reason: Some(text(
+                                    localization::choice_key(index, position, "locked-reason"),
+                                    reason,
+                                    context,
+                                )),
+                            });
+                            continue;
+                        }
+                        let additional_text = text(
+                            localization::choice_key(index, position, "additional-text"),
+                            &choice.additional_text,
+                            context,
+                        );
+                        let mut section = route(Some(position));
+                        if !additional_text.is_empty() || choice.illustration.is_some() {
+                            choice_sections.push(Section {
+                                number: first_choice + choice_sections.len(),
+                                node: Some(index),
+                                texts: texts(vec![choice_text.clone(), additional_text]),
+                                illustration: choice.illustration.as_ref().map(image),
+                                exits: Exits::TurnTo(section),
+                            });
+                            section = first_choice + choice_sections.len() - 1;
+                        }
+                        shown.push(Choice {
+                            text: choice_text,
+                            section: Some(section),
+                            locked_reason: None,
+                        });
+                    }
+                    Section {
+                        number: state,
+                        node: Some(index),
+                        texts: texts(vec![text(
+                            localization::node_key(index, "text"),
+                            &content.text,
+                            context,
+                        )]),
+                        illustration: None,
+                        exits: if shown.iter().any(|choice| choice.section.is_some()) {
+                            Exits::Choices(shown)
+                        } else {
+                            Exits::TurnTo(route(None))
+                        },
+                    }
+                }
+            };
+            sections.push(section);
+        }
+        if lost {
+            sections.push(Section {
+                number: lost_section,
+                node: None,
+                texts: vec![locales.ui(language, "story-lost")],
+                illustration: None,
+                exits: Exits::End,
+            });
+        }
+        sections.append(&mut choice_sections);
+
+        // The start is always the first section.
+        let mut numbers: Vec<usize> = (2..=sections.len()).collect();
+        numbers.shuffle(&mut StdRng::seed_from_u64(SEED));
+        numbers.insert(0, 1);
+        for section in sections.iter_mut() {
+            section.number = numbers[section.number];
+            match &mut section.exits {
+                Exits::TurnTo(to) => *to = numbers[*to],
+                Exits::Choices(choices) => {
+                    for to in choices
+                        .iter_mut()
+                        .filter_map(|choice| choice.section.as_mut())
+                    {
+                        *to = numbers[*to];
+                    }
+                }
+                Exits::End => {}
+            }
+        }
+        sections.sort_by_key(|section| section.number);
+
+        Ok(Self {
+            sections,
+            language,
+            palette,
+            turn_to: locales.ui(language, "gamebook-turn-to"),
+            the_end: locales.ui(language, "gamebook-the-end"),
+        })
+    }
+
+    /// The story at `path`, with every `.ftl` file of the folder of each language in `locales`.
+    /// In the language with the given code, like `ca`, or in the one the story is written in.
+    pub fn load(
+        path: impl AsRef<Path>,
+        locales: impl AsRef<Path>,
+        language: Option<&str>,
+        images: &Path,
+    ) -> Result<Self, GamebookError> {
+        let language = match language {
+            Some(code) => Language::from_code(code)
+                .ok_or_else(|| GamebookError::UnknownLanguage(code.to_string()))?,
+            None => Language::DEFAULT,
+        };
+        let text = fs::read_to_string(path)
+            .map_err(|error| GamebookError::Story(StoryError::Io(error)))?;
+        let story = StoryFile::from_ron(&text).map_err(GamebookError::Story)?;
+        let translations = localization::read_translations(locales.as_ref())
+            .map_err(GamebookError::Localization)?;
+        Self::new(&story, translations, language, images)
+    }
+
+    /// Sections link to each other by their number.
+    pub fn to_markdown(&self) -> String {
+        let link = |number: usize| format!("{} [{number}](#{number})", self.turn_to);
+        let mut markdown = String::new();
+        writeln!(markdown, "# {TITLE}").unwrap();
+        for section in self.sections.iter() {
+            writeln!(markdown, "\n## {}\n", section.number).unwrap();
+            for text in section.texts.iter() {
+                writeln!(markdown, "{}\n", markdown_text(text, &self.palette)).unwrap();
+            }
+            if let Some(path) = &section.illustration {
+                writeln!(markdown, "![](<{}>)\n", path.display()).unwrap();
+            }
+            match &section.exits {
+                Exits::TurnTo(to) => writeln!(markdown, "{}.", link(*to)).unwrap(),
+                Exits::Choices(choices) => {
+                    for choice in choices.iter() {
+                        let text = markdown_text(&choice.text, &self.palette);
+                        match (choice.section, &choice.locked_reason) {
+                            (Some(to), _) => writeln!(markdown, "- {text} → {}", link(to)),
+                            (None, Some(reason)) if !reason.is_empty() => writeln!(
+                                markdown,
+                                "- ~~{text}~~ ({})",
+                                markdown_text(reason, &self.palette)
+                            ),
+                            (None, _) => writeln!(markdown, "- ~~{text}~~"),
+                        }
+                        .unwrap();
+                    }
+                }
+                Exits::End => writeln!(markdown, "**{}**", self.the_end).unwrap(),
+            }
+        }
+        markdown
+    }
+
+    /// A page that can be opened and printed from the browser, with the illustrations linked
+    /// by their path.
+    pub fn to_html(&self) -> String {
+        let link = |number: usize| {
+            format!(
+                "{} <a href=\"#section-{number}\">{number}</a>",
+                escape_html(&self.turn_to)
+            )
+        };
+        let mut html = String::new();
+        writeln!(html, "<!DOCTYPE html>").unwrap();
+        writeln!(html, "<html lang=\"{}\">", self.language.code()).unwrap();
+        writeln!(html, "<head>\n<meta charset=\"utf-8\">").unwrap();
+        writeln!(html, "<title>{TITLE}</title>").unwrap();
+        writeln!(html, "<style>{STYLE}</style>\n</head>\n<body>").unwrap();
+        writeln!(html, "<h1>{TITLE}</h1>").unwrap();
+        for section in self.sections.iter() {
+            writeln!(
+                html,
+                "<section id=\"section-{0}\">\n<h2>{0}</h2>",
+                section.number
+            )
+            .unwrap();
+            for text in section.texts.iter() {
+                writeln!(html, "<p>{}</p>", html_text(text, &self.palette)).unwrap();
+            }
+            if let Some(path) = &section.illustration {
+                let path = escape_html(&path.display().to_string());
+                writeln!(html, "<img src=\"{path}\" alt=\"\">").unwrap();
+            }
+            match &section.exits {
+                Exits::TurnTo(to) => {
+                    writeln!(html, "<p class=\"turn\">{}.</p>", link(*to)).unwrap()
+                }
+                Exits::Choices(choices) => {
+                    writeln!(html, "<ul>").unwrap();
+                    for choice in choices.iter() {
+                        let text = html_text(&choice.text, &self.palette);
+                        match (choice.section, &choice.locked_reason) {
+                            (Some(to), _) => writeln!(html, "<li>{text} → {}</li>", link(to)),
+                            (None, Some(reason)) if !reason.is_empty() => writeln!(
+                                html,
+                                "<li class=\"locked\">{text} <em>({})</em></li>",
+                                html_text(reason, &self.palette)
+                            ),
+                            (None, _) => writeln!(html, "<li class=\"locked\">{text}</li>"),
+                        }
+                        .unwrap();
+                    }
+                    writeln!(html, "</ul>").unwrap();
+                }
+                Exits::End => {
+                    writeln!(html, "<p class=\"end\">{}</p>", escape_html(&self.the_end)).unwrap()
+                }
+            }
+            writeln!(html, "</section>").unwrap();
+        }
+        writeln!(html, "</body>\n</html>").unwrap();
+        html
+    }
+}
+
+const STYLE: &str = "
+body { font-family: Georgia, serif; max-width: 40em; margin: auto; padding: 1em; }
+h1, h2, .end { text-align: center; }
+section { break-inside: avoid; margin-bottom: 2em; }
+img { display: block; max-width: 60%; max-height: 20em; margin: 1em auto; }
+.locked { color: gray; }
+";
+
+/// Plays every path and keeps each node once per context it's read with.
+fn unfold(graph: &BookGraph) -> Unfolded {
+    let exploration = graph.explore();
+    let mut states: Vec<(usize, BookContext)> = Vec::new();
+    let mut state = |node: usize, context: &BookContext| {
+        states
+            .iter()
+            .position(|(other_node, other_context)| *other_node == node && other_context == context)
+            .unwrap_or_else(|| {
+                states.push((node, context.clone()));
+                states.len() - 1
+            })
+    };
+    let mut routes = HashMap::new();
+    let mut lost = false;
+    for playthrough in exploration.playthroughs.iter() {
+        let steps = &playthrough.steps;
+        let ids: Vec<usize> = steps
+            .iter()
+            .map(|step| state(step.node, &step.context))
+            .collect();
+        for (pair, ids) in steps.windows(2).zip(ids.windows(2)) {
+            routes.insert((ids[0], pair[0].choice), Some(ids[1]));
+        }
+        let to = match playthrough.end {
+            PlaythroughEnd::Loop(to) => Some(state(to, &playthrough.context)),
+            // A fork without any choice to take loses the story, like it does in the game.
+            PlaythroughEnd::MissingNode(_)
+            | PlaythroughEnd::ChoiceFailed { .. }
+            | PlaythroughEnd::DeadEnd(_)
+            | PlaythroughEnd::TooLong => {
+                lost = true;
+                None
+            }
+            PlaythroughEnd::Ending(_) => continue,
+        };
+        if let (Some(last), Some(&id)) = (steps.last(), ids.last()) {
+            routes.insert((id, last.choice), to);
+        }
+    }
+    Unfolded {
+        states,
+        routes,
+        lost,
+    }
+}
+
+/// The spans of the markup, or the text as it is if the markup is wrong.
+fn spans(source: &str, palette: &Palette) -> Vec<Span> {
+    markup::parse(source, palette).unwrap_or_else(|_| {
+        vec![Span {
+            text: source.to_string(),
+            ..Default::default()
+        }]
+    })
+}
+
+/// Highlights in bold. Colours are left out, Markdown has none.
+fn markdown_text(source: &str, palette: &Palette) -> String {
+    let mut markdown = String::new();
+    for span in spans(source, palette) {
+        let mut text = String::new();
+        for c in span.text.chars() {
+            if "\\`*_[]<>#~".contains(c) {
+                text.push('\\');
+            }
+            text.push(c);
+        }
+        if span.italic {
+            text = around_words(&text, "*");
+        }
+        if span.highlighted {
+            text = around_words(&text, "**");
+        }
+        markdown.push_str(&text.replace('\n', "\\\n"));
+    }
+    markdown
+}
+
+/// Markdown emphasis can't start nor end with whitespace.
+fn around_words(text: &str, marker: &str) -> String {
+    let words = text.trim();
+    if words.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + words.len();
+    format!("{}{marker}{words}{marker}{}", &text[..start], &text[end..])
+}
+
+/// Highlights in bold and in their colour.
+fn html_text(source: &str, palette: &Palette) -> String {
+    let mut html = String::new();
+    for span in spans(source, palette) {
+        let mut text = escape_html(&span.text).replace('\n', "<br>");
+        if span.italic {
+            text = format!("<em>{text}</em>");
+        }
+        if span.highlighted {
+            text = format!("<strong>{text}</strong>");
+        }
+        if let Some(color) = span.color {
+            let [r, g, b, _] = color.as_rgba_u8();
+            text = format!("<span style=\"color: #{r:02x}{g:02x}{b:02x}\">{text}</span>");
+        }
+        html.push_str(&text);
+    }
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nodes_are_written_once_per_context() {
+        let story = StoryFile::from_ron(
+            r#"(
+                variables: {"valiente": Bool(false)},
+                nodes: [
+                    (0, Fork(
+                        content: (text: "El *dragón* ruge"),
+                        choices: [
+                            (
+                                text: "Luchar",
+                                additional_text: "Sant Jordi desenvaina",
+                                state_change: "valiente = true",
+                                next: 1,
+                            ),
+                            (text: "Huir", next: 1),
+                        ],
+                    )),
+                    (1, Simple(
+                        content: (text: "Amanece"),
+                        next: Some(2),
+                    )),
+                    (2, Fork(
+                        content: (text: "¿Y ahora?"),
+                        choices: [(text: "Volver", next: "if valiente { 3 } else { 4 }")],
+                    )),
+                    (3, Simple(content: (text: "La rosa"), next: None)),
+                    (4, Simple(content: (text: "El libro"), next: None)),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let gamebook = Gamebook::new(&story, [], Language::DEFAULT, Path::new("assets")).unwrap();
+        let sections = &gamebook.sections;
+
+        // Nodes 1 and 2 twice, once brave and once not, and the choice to fight.
+        let numbers: Vec<usize> = sections.iter().map(|section| section.number).collect();
+        assert_eq!(numbers, (1..=8).collect::<Vec<_>>());
+        let mut nodes: Vec<Option<usize>> = sections.iter().map(|section| section.node).collect();
+        nodes.sort();
+        let twice = [Some(0), Some(0), Some(1), Some(1), Some(2), Some(2)];
+        assert_eq!(nodes, [&twice[..], &[Some(3), Some(4)]].concat());
+
+        // Both ways of going back get to their own ending.
+        let section = |number: usize| &sections[number - 1];
+        assert_eq!(section(1).node, Some(0));
+        let Exits::Choices(choices) = &section(1).exits else {
+            panic!("The start is a fork");
+        };
+        let endings: Vec<usize> = choices
+            .iter()
+            .map(|choice| {
+                let mut number = choice.section.unwrap();
+                loop {
+                    match &section(number).exits {
+                        Exits::TurnTo(to) => number = *to,
+                        Exits::Choices(choices) => number = choices[0].section.unwrap(),
+                        Exits::End => return section(number).node.unwrap(),
+                    }
+                }
+            })
+            .collect();
+        assert_eq!(endings, vec![3, 4]);
+
+        let markdown = gamebook.to_markdown();
+        assert!(markdown.contains("El **dragón** ruge"));
+        assert!(gamebook
+            .to_html()
+            .contains("El <strong>dragón</strong> ruge"));
+    }
+
+    #[test]
+    fn forks_without_a_choice_to_take_lose_the_story() {
+        let story = StoryFile::from_ron(
+            r#"(nodes: [
+                (0, Fork(content: (text: "Una puerta"), choices: [
+                    (text: "Abrir", next: 1, availability: Locked(condition: "false", reason: "Cerrada")),
+                ])),
+                (1, Simple(content: (text: "Fin"), next: None)),
+            ])"#,
+        )
+        .unwrap();
+        let gamebook = Gamebook::new(&story, [], Language::DEFAULT, Path::new("assets")).unwrap();
+        let lost = gamebook
+            .sections
+            .iter()
+            .find(|section| section.node.is_none())
+            .expect("The story can get lost");
+        assert_eq!(lost.exits, Exits::End);
+        assert_eq!(gamebook.sections[0].exits, Exits::TurnTo(lost.number));
+    }
+}
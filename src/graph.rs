@@ -1,18 +1,26 @@
 #![allow(dead_code)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
+use std::iter;
+use std::mem;
 
 use bevy::{log, prelude::*};
+use serde::{Deserialize, Serialize};
 
 /// Graph where nodes are added along with their indices.
 /// When adding nodes, you must be sure that they're all connected.
+/// Use [`Graph::validate`] to check it.
 /// The starting node must be added with index 0.
 #[derive(Resource)]
 pub struct Graph<Content, Simple, Choice, Context> {
     nodes: HashMap<usize, Node<Content, Simple, Choice>>,
     current_node: usize,
     pub context: Context,
+    /// What the context starts as, also when exploring.
+    initial_context: Context,
+    /// Nodes left behind, along with the context they were left with.
+    history: Vec<(usize, Context)>,
 }
 
 impl<
@@ -32,24 +40,32 @@ impl<
             graph: &Graph<Content, Simple, Choice, Context>,
             node_index: usize,
             depth: usize,
+            printed: &mut HashSet<usize>,
             f: &mut fmt::Formatter<'_>,
         ) -> fmt::Result {
             if let Some(node) = graph.nodes.get(&node_index) {
                 for _ in 0..depth {
                     write!(f, "\t")?;
                 }
+                // Shared nodes and cycles are only printed once.
+                if !printed.insert(node_index) {
+                    return write!(f, "(see {})", node_index);
+                }
                 write!(f, "{:?}", node)?;
                 match node {
                     Node::Simple { next, .. } => {
                         if let Some(next) = next {
                             writeln!(f)?;
-                            print_node(graph, *next, depth + 1, f)?;
+                            print_node(graph, *next, depth + 1, printed, f)?;
                         }
                     }
                     Node::Fork { choices, .. } => {
                         for choice in choices.iter() {
                             writeln!(f)?;
-                            print_node(graph, choice.next_node(&graph.context), depth + 1, f)?;
+                            match choice.next_node(&graph.context) {
+                                Ok(next) => print_node(graph, next, depth + 1, printed, f)?,
+                                Err(message) => write!(f, "(fails: {})", message)?,
+                            }
                         }
                     }
                 };
@@ -58,17 +74,18 @@ impl<
         }
 
         if self.nodes.contains_key(&0) {
-            print_node(self, 0, 0, f)?;
+            print_node(self, 0, 0, &mut HashSet::new(), f)?;
         }
 
         Ok(())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Node<Content, Simple, Choice> {
     Simple {
         content: Content,
+        #[serde(default)]
         extra: Simple,
         next: Option<usize>,
     },
@@ -78,38 +95,161 @@ pub enum Node<Content, Simple, Choice> {
     },
 }
 
-#[macro_export]
-macro_rules! arc {
-    (|$closure_var:ident| $inner:expr) => {
-        Arc::new(|$closure_var| $inner)
-    };
-    ($inner:expr) => {
-        Arc::new(|_| $inner)
-    };
+/// A structural problem found by [`Graph::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GraphProblem {
+    /// There's no node with index 0.
+    MissingStart,
+    /// A simple node's `next` points to no node.
+    MissingNext { node: usize, next: usize },
+    /// A fork's choice leads to no node.
+    MissingChoiceNext {
+        node: usize,
+        choice: usize,
+        next: usize,
+    },
+    /// A fork's choice failed to route or to change the state.
+    ChoiceFailed { node: usize, choice: usize },
+    /// The node can't be reached from the start.
+    Unreachable { node: usize },
+}
+
+/// Why moving through the graph failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// There's no node with this index.
+    MissingNode(usize),
+    /// Tried to advance from a fork.
+    NotSimple(usize),
+    /// Tried to choose on a simple node.
+    NotFork(usize),
+    /// The fork doesn't have that many choices.
+    ChoiceOutOfRange {
+        node: usize,
+        choice: usize,
+        choices: usize,
+    },
+    /// The choice can't be taken with the current context.
+    ChoiceUnavailable { node: usize, choice: usize },
+    /// No choice of the fork can be taken with the current context, they're all hidden or locked.
+    DeadEnd(usize),
+    /// The choice failed to route or to change the state.
+    ChoiceFailed {
+        node: usize,
+        choice: usize,
+        message: String,
+    },
 }
 
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::MissingNode(node) => write!(f, "There's no node {}.", node),
+            GraphError::NotSimple(node) => write!(f, "Node {} was not simple.", node),
+            GraphError::NotFork(node) => write!(f, "Node {} was not a fork.", node),
+            GraphError::ChoiceOutOfRange {
+                node,
+                choice,
+                choices,
+            } => write!(
+                f,
+                "Node {} has {} choices, there's no choice {}.",
+                node, choices, choice
+            ),
+            GraphError::ChoiceUnavailable { node, choice } => {
+                write!(f, "Choice {} of node {} is not available.", choice, node)
+            }
+            GraphError::DeadEnd(node) => {
+                write!(f, "Node {} has no choice that can be taken.", node)
+            }
+            GraphError::ChoiceFailed {
+                node,
+                choice,
+                message,
+            } => write!(f, "Choice {} of node {} failed: {}", choice, node, message),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
 pub trait ChoiceTrait<Context> {
-    fn next_node(&self, context: &Context) -> usize;
-    fn change_state(&self, context: &mut Context);
+    /// Fails with a message saying why if there's no way of knowing where the choice leads.
+    fn next_node(&self, context: &Context) -> Result<usize, String>;
+    /// Fails with a message saying why if the state can't be changed.
+    fn change_state(&self, context: &mut Context) -> Result<(), String>;
+
+    /// Whether or not the choice can be taken.
+    fn is_available(&self, _context: &Context) -> bool {
+        true
+    }
 }
 
-impl<Content, Simple, Choice: ChoiceTrait<Context> + Clone, Context: Default>
+impl<Content, Simple, Choice: ChoiceTrait<Context> + Clone, Context: Default + Clone>
     Graph<Content, Simple, Choice, Context>
 {
     pub fn new() -> Self {
+        Self::with_context(Context::default())
+    }
+
+    /// For contexts that depend on the story, like ones with variables declared by it.
+    pub fn with_context(context: Context) -> Self {
         Self {
             nodes: HashMap::new(),
             current_node: 0,
-            context: Default::default(),
+            context: context.clone(),
+            initial_context: context,
+            history: Vec::new(),
         }
     }
 
+    pub fn get_initial_context(&self) -> &Context {
+        &self.initial_context
+    }
+
     pub fn add_node(&mut self, index: usize, node: Node<Content, Simple, Choice>) {
         self.nodes.insert(index, node);
     }
 
     pub fn get_current_node(&self) -> &Node<Content, Simple, Choice> {
-        self.nodes.get(&self.current_node).unwrap()
+        self.try_get_current_node()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_get_current_node(&self) -> Result<&Node<Content, Simple, Choice>, GraphError> {
+        self.nodes
+            .get(&self.current_node)
+            .ok_or(GraphError::MissingNode(self.current_node))
+    }
+
+    /// Like [`Self::try_get_current_node`], but a fork without any available choice fails too,
+    /// as there would be no way on from it.
+    pub fn try_get_playable_node(&self) -> Result<&Node<Content, Simple, Choice>, GraphError> {
+        let node = self.try_get_current_node()?;
+        if let Node::Fork { choices, .. } = node {
+            if !choices
+                .iter()
+                .any(|choice| choice.is_available(&self.context))
+            {
+                return Err(GraphError::DeadEnd(self.current_node));
+            }
+        }
+        Ok(node)
+    }
+
+    pub fn get_node(&self, index: usize) -> Option<&Node<Content, Simple, Choice>> {
+        self.nodes.get(&index)
+    }
+
+    /// Indices of all nodes, in order.
+    pub fn get_indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = self.nodes.keys().copied().collect();
+        indices.sort();
+        indices
+    }
+
+    pub fn get_current_index(&self) -> usize {
+        self.current_node
     }
 
     pub fn set_current_node(&mut self, index: usize) {
@@ -118,6 +258,51 @@ impl<Content, Simple, Choice: ChoiceTrait<Context> + Clone, Context: Default>
 
     pub fn reset(&mut self) {
         self.current_node = 0;
+        self.history.clear();
+    }
+
+    /// Nodes left behind, oldest first, along with the context they were left with.
+    pub fn get_history(&self) -> &[(usize, Context)] {
+        &self.history
+    }
+
+    /// Puts the graph back in a previous state, like one from [`Graph::get_history`].
+    /// Nothing changes if any of the nodes doesn't exist.
+    /// Returns whether or not the state was restored.
+    pub fn restore(
+        &mut self,
+        current_node: usize,
+        context: Context,
+        history: Vec<(usize, Context)>,
+    ) -> bool {
+        let all_exist = iter::once(current_node)
+            .chain(history.iter().map(|(index, _)| *index))
+            .all(|index| self.nodes.contains_key(&index));
+        if all_exist {
+            self.current_node = current_node;
+            self.context = context;
+            self.history = history;
+        }
+        all_exist
+    }
+
+    /// Returns whether or not there's a node to go back to.
+    pub fn can_go_back(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// Returns to the previous node, restoring the context it was left with.
+    /// Returns whether or not there was a node to go back to.
+    pub fn go_back(&mut self) -> bool {
+        match self.history.pop() {
+            Some((index, context)) => {
+                log::info!("Current index: {}", index);
+                self.current_node = index;
+                self.context = context;
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn get_content(&mut self) -> &Content {
@@ -134,30 +319,365 @@ impl<Content, Simple, Choice: ChoiceTrait<Context> + Clone, Context: Default>
 
     /// Current node must be simple.
     pub fn advance(&mut self) {
-        match self.get_current_node() {
-            Node::Simple { next, .. } => {
-                if let Some(next_index) = next {
-                    log::info!("Current index: {}", next_index);
-                    self.current_node = *next_index;
-                }
+        self.try_advance()
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Like [`Graph::advance`], but returns an error instead of panicking.
+    /// Nothing changes if there's an error.
+    pub fn try_advance(&mut self) -> Result<(), GraphError> {
+        let next = match self.try_get_current_node()? {
+            Node::Simple { next, .. } => *next,
+            Node::Fork { .. } => return Err(GraphError::NotSimple(self.current_node)),
+        };
+        if let Some(next_index) = next {
+            if !self.nodes.contains_key(&next_index) {
+                return Err(GraphError::MissingNode(next_index));
             }
-            _ => panic!("Current node was not simple."),
+            log::info!("Current index: {}", next_index);
+            self.history.push((self.current_node, self.context.clone()));
+            self.current_node = next_index;
         }
+        Ok(())
     }
 
     /// Current node must be a fork.
     pub fn choose(&mut self, index: usize) {
-        let (next_node, choice) = match self.get_current_node() {
+        self.try_choose(index)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Like [`Graph::choose`], but returns an error instead of panicking.
+    /// Nothing changes if there's an error.
+    pub fn try_choose(&mut self, index: usize) -> Result<(), GraphError> {
+        let choice = match self.try_get_current_node()? {
             Node::Fork { choices, .. } => {
-                let next = choices[index].next_node(&self.context);
-                log::info!("Current index: {}", next);
-                let choice = choices[index].clone();
-                (next, choice)
+                choices
+                    .get(index)
+                    .cloned()
+                    .ok_or(GraphError::ChoiceOutOfRange {
+                        node: self.current_node,
+                        choice: index,
+                        choices: choices.len(),
+                    })?
             }
-            _ => panic!("Current node was not a fork."),
+            Node::Simple { .. } => return Err(GraphError::NotFork(self.current_node)),
         };
-        choice.change_state(&mut self.context);
+        if !choice.is_available(&self.context) {
+            return Err(GraphError::ChoiceUnavailable {
+                node: self.current_node,
+                choice: index,
+            });
+        }
+        let mut context = self.context.clone();
+        let next_node =
+            take_choice(&choice, &mut context).map_err(|message| GraphError::ChoiceFailed {
+                node: self.current_node,
+                choice: index,
+                message,
+            })?;
+        if !self.nodes.contains_key(&next_node) {
+            return Err(GraphError::MissingNode(next_node));
+        }
+        log::info!("Current index: {}", next_node);
+        let previous_context = mem::replace(&mut self.context, context);
+        self.history.push((self.current_node, previous_context));
         self.current_node = next_node;
+        Ok(())
+    }
+}
+
+/// Playthroughs longer than this are cut short by [`Graph::explore`].
+/// Guards against contexts that keep changing on a cycle, like counters.
+pub const MAX_PLAYTHROUGH_LENGTH: usize = 1000;
+
+/// A node visited in a [`Playthrough`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<Context> {
+    pub node: usize,
+    /// The choice taken, if the node is a fork.
+    pub choice: Option<usize>,
+    /// The context the node was shown with.
+    pub context: Context,
+}
+
+/// How a [`Playthrough`] finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaythroughEnd {
+    /// Reached a simple node without `next`.
+    Ending(usize),
+    /// Came back to a node already on the path with the same context.
+    Loop(usize),
+    /// Reached a fork without available choices.
+    DeadEnd(usize),
+    /// Tried to go to a node that doesn't exist.
+    MissingNode(usize),
+    /// A choice failed to route or to change the state.
+    ChoiceFailed {
+        node: usize,
+        choice: usize,
+        message: String,
+    },
+    /// Hit [`MAX_PLAYTHROUGH_LENGTH`].
+    TooLong,
+}
+
+/// A move from one node to another, as found by [`Graph::explore`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edge {
+    pub from: usize,
+    /// The choice taken, if `from` is a fork.
+    pub choice: Option<usize>,
+    /// Might not exist, see [`PlaythroughEnd::MissingNode`].
+    pub to: usize,
+}
+
+/// A path through the graph, as found by [`Graph::explore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playthrough<Context> {
+    pub steps: Vec<Step<Context>>,
+    pub end: PlaythroughEnd,
+    /// The context after the last step.
+    pub context: Context,
+}
+
+impl<Context> Playthrough<Context> {
+    /// The nodes visited, in order.
+    pub fn nodes(&self) -> Vec<usize> {
+        self.steps.iter().map(|step| step.node).collect()
+    }
+}
+
+/// Every playthrough of a graph, as returned by [`Graph::explore`].
+#[derive(Debug, Clone)]
+pub struct Exploration<Context> {
+    pub playthroughs: Vec<Playthrough<Context>>,
+}
+
+impl<Context> Exploration<Context> {
+    /// Ending nodes that can be reached.
+    pub fn endings(&self) -> BTreeSet<usize> {
+        self.playthroughs
+            .iter()
+            .filter_map(|playthrough| match playthrough.end {
+                PlaythroughEnd::Ending(node) => Some(node),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Playthroughs that didn't finish on an ending.
+    pub fn broken(&self) -> impl Iterator<Item = &Playthrough<Context>> {
+        self.playthroughs
+            .iter()
+            .filter(|playthrough| !matches!(playthrough.end, PlaythroughEnd::Ending(_)))
+    }
+
+    /// Every node visited by some playthrough.
+    pub fn visited(&self) -> HashSet<usize> {
+        self.playthroughs
+            .iter()
+            .flat_map(|playthrough| playthrough.steps.iter().map(|step| step.node))
+            .collect()
+    }
+
+    /// Every move taken by some playthrough.
+    /// A choice that routes depending on the context shows up once per node it leads to.
+    pub fn edges(&self) -> BTreeSet<Edge> {
+        let mut edges = BTreeSet::new();
+        for playthrough in self.playthroughs.iter() {
+            let steps = &playthrough.steps;
+            for pair in steps.windows(2) {
+                edges.insert(Edge {
+                    from: pair[0].node,
+                    choice: pair[0].choice,
+                    to: pair[1].node,
+                });
+            }
+            if let (Some(last), PlaythroughEnd::Loop(to) | PlaythroughEnd::MissingNode(to)) =
+                (steps.last(), &playthrough.end)
+            {
+                edges.insert(Edge {
+                    from: last.node,
+                    choice: last.choice,
+                    to: *to,
+                });
+            }
+        }
+        edges
+    }
+
+    /// The first context some playthrough shows `node` with.
+    pub fn context_at(&self, node: usize) -> Option<&Context> {
+        self.playthroughs
+            .iter()
+            .flat_map(|playthrough| playthrough.steps.iter())
+            .find(|step| step.node == node)
+            .map(|step| &step.context)
+    }
+}
+
+/// Routes `choice` and applies its state change, in the same order as [`Graph::choose`].
+fn take_choice<Choice: ChoiceTrait<Context>, Context>(
+    choice: &Choice,
+    context: &mut Context,
+) -> Result<usize, String> {
+    let next = choice.next_node(context)?;
+    choice.change_state(context)?;
+    Ok(next)
+}
+
+impl<Content, Simple, Choice: ChoiceTrait<Context>, Context: Default + Clone + PartialEq>
+    Graph<Content, Simple, Choice, Context>
+{
+    /// Plays every possible path from the start with the initial context.
+    /// The context is cloned at each fork and every choice is tried, routing and changing
+    /// the state just like [`Graph::choose`] does.
+    /// Since `next` can depend on the context, this is the only way of knowing the real routing.
+    pub fn explore(&self) -> Exploration<Context> {
+        let mut playthroughs = Vec::new();
+        // Failed choices are queued too so playthroughs come out in the order of the choices.
+        let mut pending = vec![(Ok(0), self.initial_context.clone(), Vec::new())];
+        while let Some((next, context, mut steps)) = pending.pop() {
+            let mut finish = |steps, end, context| {
+                playthroughs.push(Playthrough {
+                    steps,
+                    end,
+                    context,
+                })
+            };
+            let index = match next {
+                Ok(index) => index,
+                Err(end) => {
+                    finish(steps, end, context);
+                    continue;
+                }
+            };
+            if steps.len() >= MAX_PLAYTHROUGH_LENGTH {
+                finish(steps, PlaythroughEnd::TooLong, context);
+                continue;
+            }
+            if steps
+                .iter()
+                .any(|step: &Step<Context>| step.node == index && step.context == context)
+            {
+                finish(steps, PlaythroughEnd::Loop(index), context);
+                continue;
+            }
+            let Some(node) = self.nodes.get(&index) else {
+                finish(steps, PlaythroughEnd::MissingNode(index), context);
+                continue;
+            };
+            match node {
+                Node::Simple { next, .. } => {
+                    steps.push(Step {
+                        node: index,
+                        choice: None,
+                        context: context.clone(),
+                    });
+                    match next {
+                        Some(next) => pending.push((Ok(*next), context, steps)),
+                        None => finish(steps, PlaythroughEnd::Ending(index), context),
+                    }
+                }
+                Node::Fork { choices, .. }
+                    if !choices.iter().any(|choice| choice.is_available(&context)) =>
+                {
+                    steps.push(Step {
+                        node: index,
+                        choice: None,
+                        context: context.clone(),
+                    });
+                    finish(steps, PlaythroughEnd::DeadEnd(index), context);
+                }
+                Node::Fork { choices, .. } => {
+                    // Reversed so the first choice is explored first.
+                    for (choice_index, choice) in choices.iter().enumerate().rev() {
+                        if !choice.is_available(&context) {
+                            continue;
+                        }
+                        let mut steps = steps.clone();
+                        steps.push(Step {
+                            node: index,
+                            choice: Some(choice_index),
+                            context: context.clone(),
+                        });
+                        let mut next_context = context.clone();
+                        match take_choice(choice, &mut next_context) {
+                            Ok(next) => pending.push((Ok(next), next_context, steps)),
+                            Err(message) => pending.push((
+                                Err(PlaythroughEnd::ChoiceFailed {
+                                    node: index,
+                                    choice: choice_index,
+                                    message,
+                                }),
+                                context.clone(),
+                                steps,
+                            )),
+                        }
+                    }
+                }
+            }
+        }
+        Exploration { playthroughs }
+    }
+
+    /// Checks that the graph is sound, returning every problem found.
+    /// Choices are checked with the routing found by [`Graph::explore`].
+    pub fn validate(&self) -> Vec<GraphProblem> {
+        let mut problems = BTreeSet::new();
+
+        let mut indices: Vec<usize> = self.nodes.keys().copied().collect();
+        indices.sort();
+        for index in indices.iter() {
+            if let Node::Simple {
+                next: Some(next), ..
+            } = self.nodes[index]
+            {
+                if !self.nodes.contains_key(&next) {
+                    problems.insert(GraphProblem::MissingNext { node: *index, next });
+                }
+            }
+        }
+
+        if !self.nodes.contains_key(&0) {
+            problems.insert(GraphProblem::MissingStart);
+            return problems.into_iter().collect();
+        }
+
+        let exploration = self.explore();
+        for playthrough in exploration.playthroughs.iter() {
+            match playthrough.end {
+                PlaythroughEnd::MissingNode(next) => {
+                    // Missing `next`s of simple nodes were already reported.
+                    if let Some(Step {
+                        node,
+                        choice: Some(choice),
+                        ..
+                    }) = playthrough.steps.last()
+                    {
+                        problems.insert(GraphProblem::MissingChoiceNext {
+                            node: *node,
+                            choice: *choice,
+                            next,
+                        });
+                    }
+                }
+                PlaythroughEnd::ChoiceFailed { node, choice, .. } => {
+                    problems.insert(GraphProblem::ChoiceFailed { node, choice });
+                }
+                _ => {}
+            }
+        }
+
+        let visited = exploration.visited();
+        for index in indices {
+            if !visited.contains(&index) {
+                problems.insert(GraphProblem::Unreachable { node: index });
+            }
+        }
+
+        problems.into_iter().collect()
     }
 }
 
@@ -180,14 +700,16 @@ mod tests {
     }
 
     impl ChoiceTrait<TestContext> for TestChoice {
-        fn next_node(&self, _: &TestContext) -> usize {
-            self.next
+        fn next_node(&self, _: &TestContext) -> Result<usize, String> {
+            Ok(self.next)
         }
 
-        fn change_state(&self, _: &mut TestContext) {}
+        fn change_state(&self, _: &mut TestContext) -> Result<(), String> {
+            Ok(())
+        }
     }
 
-    #[derive(Debug, Default)]
+    #[derive(Debug, Default, Clone, PartialEq)]
     struct TestContext {
         some_flag: bool,
     }
@@ -281,4 +803,366 @@ mod tests {
         texts.push(graph.get_content().text.clone()); // B
         assert_eq!(texts, vec!["Z", "A", "B"]);
     }
+
+    #[test]
+    fn validate_reports_problems() {
+        let mut graph = Graph::<TestContent, TestSimple, TestChoice, TestContext>::new();
+        let simple = |text: &str, next| Node::Simple {
+            content: TestContent {
+                text: text.to_string(),
+            },
+            extra: (),
+            next,
+        };
+        let choice = |next| TestChoice {
+            title: String::new(),
+            subtitle: String::new(),
+            next,
+        };
+        assert_eq!(graph.validate(), vec![GraphProblem::MissingStart]);
+
+        graph.add_node(0, simple("Z", Some(1)));
+        graph.add_node(
+            1,
+            Node::Fork {
+                content: TestContent {
+                    text: "A".to_string(),
+                },
+                choices: vec![choice(2), choice(5)],
+            },
+        );
+        graph.add_node(2, simple("B", Some(6)));
+        graph.add_node(3, simple("C", None));
+        assert_eq!(
+            graph.validate(),
+            vec![
+                GraphProblem::MissingNext { node: 2, next: 6 },
+                GraphProblem::MissingChoiceNext {
+                    node: 1,
+                    choice: 1,
+                    next: 5
+                },
+                GraphProblem::Unreachable { node: 3 },
+            ]
+        );
+
+        graph.add_node(2, simple("B", Some(0)));
+        graph.add_node(5, simple("D", Some(3)));
+        assert_eq!(graph.validate(), vec![]);
+    }
+
+    #[derive(Clone)]
+    struct RoutedChoice {
+        next: fn(&TestContext) -> Result<usize, String>,
+        change: fn(&mut TestContext),
+    }
+
+    impl ChoiceTrait<TestContext> for RoutedChoice {
+        fn next_node(&self, context: &TestContext) -> Result<usize, String> {
+            (self.next)(context)
+        }
+
+        fn change_state(&self, context: &mut TestContext) -> Result<(), String> {
+            (self.change)(context);
+            Ok(())
+        }
+    }
+
+    /// The graph looks like this, where going from B to D needs the flag set by A's first choice:
+    ///       B - C - D
+    ///      /   /
+    /// Z - A - -
+    ///      \
+    ///       (fails)
+    #[test]
+    fn explore_finds_every_playthrough() {
+        let mut graph = Graph::<TestContent, TestSimple, RoutedChoice, TestContext>::new();
+        let simple = |text: &str, next| Node::Simple {
+            content: TestContent {
+                text: text.to_string(),
+            },
+            extra: (),
+            next,
+        };
+        let fork = |text: &str, choices| Node::Fork {
+            content: TestContent {
+                text: text.to_string(),
+            },
+            choices,
+        };
+        graph.add_node(0, simple("Z", Some(1)));
+        graph.add_node(
+            1,
+            fork(
+                "A",
+                vec![
+                    RoutedChoice {
+                        next: |_| Ok(2),
+                        change: |context| context.some_flag = true,
+                    },
+                    RoutedChoice {
+                        next: |_| Ok(3),
+                        change: |_| {},
+                    },
+                    RoutedChoice {
+                        next: |_| Err("Oops".to_string()),
+                        change: |_| {},
+                    },
+                ],
+            ),
+        );
+        graph.add_node(
+            2,
+            fork(
+                "B",
+                vec![RoutedChoice {
+                    next: |context| Ok(if context.some_flag { 4 } else { 3 }),
+                    change: |_| {},
+                }],
+            ),
+        );
+        graph.add_node(3, simple("C", None));
+        graph.add_node(4, simple("D", None));
+
+        let exploration = graph.explore();
+        let paths: Vec<(Vec<usize>, PlaythroughEnd)> = exploration
+            .playthroughs
+            .iter()
+            .map(|playthrough| (playthrough.nodes(), playthrough.end.clone()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (vec![0, 1, 2, 4], PlaythroughEnd::Ending(4)),
+                (vec![0, 1, 3], PlaythroughEnd::Ending(3)),
+                (
+                    vec![0, 1],
+                    PlaythroughEnd::ChoiceFailed {
+                        node: 1,
+                        choice: 2,
+                        message: "Oops".to_string(),
+                    }
+                ),
+            ]
+        );
+        assert_eq!(exploration.endings(), BTreeSet::from([3, 4]));
+        let edge = |from, choice, to| Edge { from, choice, to };
+        assert_eq!(
+            exploration.edges(),
+            BTreeSet::from([
+                edge(0, None, 1),
+                edge(1, Some(0), 2),
+                edge(1, Some(1), 3),
+                edge(2, Some(0), 4),
+            ])
+        );
+        assert!(exploration.playthroughs[0].context.some_flag);
+        assert!(!exploration.playthroughs[0].steps[1].context.some_flag);
+        assert_eq!(
+            graph.validate(),
+            vec![GraphProblem::ChoiceFailed { node: 1, choice: 2 }]
+        );
+    }
+
+    #[test]
+    fn go_back_restores_context() {
+        let mut graph = Graph::<TestContent, TestSimple, RoutedChoice, TestContext>::new();
+        graph.add_node(
+            0,
+            Node::Simple {
+                content: TestContent {
+                    text: "Z".to_string(),
+                },
+                extra: (),
+                next: Some(1),
+            },
+        );
+        graph.add_node(
+            1,
+            Node::Fork {
+                content: TestContent {
+                    text: "A".to_string(),
+                },
+                choices: vec![RoutedChoice {
+                    next: |_| Ok(2),
+                    change: |context| context.some_flag = true,
+                }],
+            },
+        );
+        graph.add_node(
+            2,
+            Node::Simple {
+                content: TestContent {
+                    text: "B".to_string(),
+                },
+                extra: (),
+                next: None,
+            },
+        );
+
+        assert!(!graph.can_go_back());
+        graph.advance();
+        graph.choose(0);
+        assert_eq!(graph.get_current_index(), 2);
+        assert!(graph.context.some_flag);
+        // Advancing from an ending doesn't move, so there's nothing to remember.
+        graph.advance();
+
+        assert!(graph.go_back());
+        assert_eq!(graph.get_current_index(), 1);
+        assert!(!graph.context.some_flag);
+        assert!(graph.go_back());
+        assert_eq!(graph.get_current_index(), 0);
+        assert!(!graph.go_back());
+
+        graph.advance();
+        graph.reset();
+        assert!(!graph.can_go_back());
+    }
+
+    #[test]
+    fn try_navigation_returns_errors() {
+        let mut graph = Graph::<TestContent, TestSimple, RoutedChoice, TestContext>::new();
+        assert_eq!(graph.try_advance(), Err(GraphError::MissingNode(0)));
+        graph.add_node(
+            0,
+            Node::Fork {
+                content: TestContent {
+                    text: "Z".to_string(),
+                },
+                choices: vec![
+                    RoutedChoice {
+                        next: |_| Ok(1),
+                        change: |context| context.some_flag = true,
+                    },
+                    RoutedChoice {
+                        next: |_| Ok(5),
+                        change: |context| context.some_flag = true,
+                    },
+                    RoutedChoice {
+                        next: |_| Err("Oops".to_string()),
+                        change: |_| {},
+                    },
+                ],
+            },
+        );
+        graph.add_node(
+            1,
+            Node::Simple {
+                content: TestContent {
+                    text: "A".to_string(),
+                },
+                extra: (),
+                next: Some(7),
+            },
+        );
+
+        assert_eq!(graph.try_advance(), Err(GraphError::NotSimple(0)));
+        assert_eq!(
+            graph.try_choose(3),
+            Err(GraphError::ChoiceOutOfRange {
+                node: 0,
+                choice: 3,
+                choices: 3
+            })
+        );
+        assert_eq!(graph.try_choose(1), Err(GraphError::MissingNode(5)));
+        assert_eq!(
+            graph.try_choose(2),
+            Err(GraphError::ChoiceFailed {
+                node: 0,
+                choice: 2,
+                message: "Oops".to_string(),
+            })
+        );
+        // Failing doesn't move nor change the context.
+        assert_eq!(graph.get_current_index(), 0);
+        assert!(!graph.context.some_flag);
+        assert!(!graph.can_go_back());
+
+        assert_eq!(graph.try_choose(0), Ok(()));
+        assert!(graph.context.some_flag);
+        assert_eq!(graph.try_choose(0), Err(GraphError::NotFork(1)));
+        assert_eq!(graph.try_advance(), Err(GraphError::MissingNode(7)));
+        assert_eq!(graph.get_current_index(), 1);
+    }
+
+    #[derive(Clone)]
+    struct GatedChoice {
+        next: usize,
+        needs_flag: bool,
+    }
+
+    impl ChoiceTrait<TestContext> for GatedChoice {
+        fn next_node(&self, _: &TestContext) -> Result<usize, String> {
+            Ok(self.next)
+        }
+
+        fn change_state(&self, context: &mut TestContext) -> Result<(), String> {
+            context.some_flag = true;
+            Ok(())
+        }
+
+        fn is_available(&self, context: &TestContext) -> bool {
+            !self.needs_flag || context.some_flag
+        }
+    }
+
+    /// The graph looks like this, where going to A needs the flag set by going back to Z:
+    /// Z - A
+    ///  \_/
+    #[test]
+    fn unavailable_choices_are_skipped() {
+        let mut graph = Graph::<TestContent, TestSimple, GatedChoice, TestContext>::new();
+        graph.add_node(
+            0,
+            Node::Fork {
+                content: TestContent {
+                    text: "Z".to_string(),
+                },
+                choices: vec![
+                    GatedChoice {
+                        next: 1,
+                        needs_flag: true,
+                    },
+                    GatedChoice {
+                        next: 0,
+                        needs_flag: false,
+                    },
+                ],
+            },
+        );
+        graph.add_node(
+            1,
+            Node::Simple {
+                content: TestContent {
+                    text: "A".to_string(),
+                },
+                extra: (),
+                next: None,
+            },
+        );
+
+        let paths: Vec<(Vec<usize>, PlaythroughEnd)> = graph
+            .explore()
+            .playthroughs
+            .iter()
+            .map(|playthrough| (playthrough.nodes(), playthrough.end.clone()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (vec![0, 0, 1], PlaythroughEnd::Ending(1)),
+                (vec![0, 0], PlaythroughEnd::Loop(0)),
+            ]
+        );
+
+        assert_eq!(
+            graph.try_choose(0),
+            Err(GraphError::ChoiceUnavailable { node: 0, choice: 0 })
+        );
+        graph.choose(1);
+        graph.choose(0);
+        assert_eq!(graph.get_content().text, "A");
+    }
 }
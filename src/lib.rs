@@ -3,15 +3,34 @@
 mod actions;
 mod book;
 mod book_content;
+mod controls;
+mod cues;
+pub mod dot;
+mod expression;
+mod focus;
+pub mod gamebook;
 mod graph;
+pub mod lint;
 mod loading;
+mod localization;
+mod markup;
 mod menu;
-mod utils;
+mod music;
+mod pagination;
+mod save;
+pub mod settings;
+pub mod stats;
+pub mod story;
+pub mod terminal;
+mod typewriter;
+mod variables;
 
 use crate::actions::ActionsPlugin;
 use crate::book::BookPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
+use crate::music::MusicPlugin;
+use crate::settings::SettingsPlugin;
 
 use bevy::app::App;
 use bevy::prelude::*;
@@ -42,6 +61,8 @@ impl Plugin for GamePlugin {
                 ActionsPlugin,
                 BookPlugin,
                 AudioPlugin,
+                MusicPlugin,
+                SettingsPlugin,
             ))
             .add_systems(Update, bevy::window::close_on_esc);
     }
@@ -0,0 +1,490 @@
+//! Authoring mistakes in the story and its translations, found before anyone plays it.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    book_content::Text,
+    graph::{GraphProblem, Node, PlaythroughEnd},
+    localization::{self, Message},
+    markup::{self, MarkupError, Palette},
+    menu::Language,
+    story::{self, StoryError, StoryFile},
+};
+
+/// Roughly what fits in a page with the normal font, on a 1080p screen.
+/// Longer texts get smaller and then go on in the next page, which is fine now and then.
+pub const MAX_PAGE_CHARACTERS: usize = 900;
+
+#[derive(Debug)]
+pub enum Problem {
+    /// The file couldn't be read, nothing else was checked.
+    Unreadable(String),
+    /// One of the mistakes that stop the story from loading, like a wrong expression.
+    Invalid(StoryError),
+    DuplicateNode,
+    Markup {
+        key: String,
+        error: MarkupError,
+    },
+    Format {
+        key: String,
+        error: String,
+    },
+    EmptyText {
+        field: String,
+    },
+    TooLong {
+        key: String,
+        characters: usize,
+    },
+    /// The choice is shown as text only. Only a warning, as a few choices of the book are like
+    /// that on purpose.
+    NoIllustration {
+        choice: usize,
+    },
+    MissingImage {
+        path: String,
+    },
+    MissingSound {
+        path: String,
+    },
+    OneChoice,
+    /// Every choice is hidden or locked in some playthrough.
+    Stuck,
+    /// No ending can be reached from the node.
+    NoEnding,
+    Graph(GraphProblem),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Unreadable(error) => write!(f, "{error}"),
+            // The node is already said where the lint is.
+            Problem::Invalid(StoryError::Logic { error, .. }) => write!(f, "{error}"),
+            Problem::Invalid(StoryError::InvalidColor { color, .. }) => {
+                write!(f, "uses `{color}`, which isn't a colour")
+            }
+            Problem::Invalid(error) => write!(f, "{error}"),
+            Problem::DuplicateNode => {
+                write!(f, "written more than once, the story won't load")
+            }
+            Problem::Markup { key, error } => write!(f, "`{key}` {error}"),
+            Problem::Format { key, error } => write!(f, "`{key}`: {error}"),
+            Problem::EmptyText { field } => write!(f, "the {field} is empty"),
+            Problem::TooLong { key, characters } => write!(
+                f,
+                "`{key}` has {characters} characters, more than the {MAX_PAGE_CHARACTERS} of a page"
+            ),
+            Problem::NoIllustration { choice } => write!(f, "choice {choice} has no illustration"),
+            Problem::MissingImage { path } => write!(f, "there's no image at `{path}`"),
+            Problem::MissingSound { path } => write!(f, "there's no sound at `{path}`"),
+            Problem::OneChoice => write!(f, "it's a fork with only one choice"),
+            Problem::Stuck => write!(f, "every choice is hidden or locked in some playthrough"),
+            Problem::NoEnding => write!(f, "no ending can be reached from here"),
+            Problem::Graph(problem) => match problem {
+                GraphProblem::MissingStart => write!(f, "there's no node 0 to start from"),
+                GraphProblem::MissingNext { next, .. } => {
+                    write!(f, "goes on to node {next}, which doesn't exist")
+                }
+                GraphProblem::MissingChoiceNext { choice, next, .. } => {
+                    write!(
+                        f,
+                        "choice {choice} leads to node {next}, which doesn't exist"
+                    )
+                }
+                GraphProblem::ChoiceFailed { choice, .. } => {
+                    write!(f, "choice {choice} breaks when it's taken")
+                }
+                GraphProblem::Unreachable { .. } => write!(f, "can't be reached from the start"),
+            },
+        }
+    }
+}
+
+impl Problem {
+    /// Worth a look, but the book can be played just fine with it.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Problem::NoIllustration { .. })
+    }
+}
+
+/// A [`Problem`], along with where it is.
+#[derive(Debug)]
+pub struct Lint {
+    pub file: String,
+    pub node: Option<usize>,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(node) = self.node {
+            write!(f, ": node {node}")?;
+        }
+        write!(f, ": {}", self.problem)
+    }
+}
+
+/// Lints the story at `path`, the translations of every language in `locales`
+/// and checks that the images of the story are in `assets`.
+pub fn lint_book(path: &Path, locales: &Path, assets: &Path) -> Vec<Lint> {
+    let file = path.display().to_string();
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) => {
+            return vec![Lint {
+                file,
+                node: None,
+                problem: Problem::Unreadable(error.to_string()),
+            }]
+        }
+    };
+    let (story, mut lints) = lint_story(&file, &source);
+    let Some(story) = story else {
+        return lints;
+    };
+
+    let mut images: Vec<String> = story.image_paths();
+    images.sort();
+    images.dedup();
+    for image in images {
+        if !assets.join(&image).is_file() {
+            lints.push(Lint {
+                file: file.clone(),
+                node: None,
+                problem: Problem::MissingImage { path: image },
+            });
+        }
+    }
+    let mut sounds: Vec<String> = story.sound_paths();
+    sounds.sort();
+    sounds.dedup();
+    for sound in sounds {
+        if !assets.join(&sound).is_file() {
+            lints.push(Lint {
+                file: file.clone(),
+                node: None,
+                problem: Problem::MissingSound { path: sound },
+            });
+        }
+    }
+
+    for language in Language::ALL {
+        let folder = locales.join(language.code());
+        let paths = match localization::ftl_files(&folder) {
+            Ok(paths) => paths,
+            Err(error) => {
+                lints.push(Lint {
+                    file: folder.display().to_string(),
+                    node: None,
+                    problem: Problem::Unreadable(error.to_string()),
+                });
+                continue;
+            }
+        };
+        for path in paths {
+            let file = path.display().to_string();
+            match fs::read_to_string(&path) {
+                Ok(source) => lints.extend(lint_translation(&file, &source, language, &story)),
+                Err(error) => lints.push(Lint {
+                    file,
+                    node: None,
+                    problem: Problem::Unreadable(error.to_string()),
+                }),
+            }
+        }
+    }
+    lints
+}
+
+/// Lints a story, returning it along with its lints so its translations can be linted too.
+/// There's no story if it can't even be parsed.
+pub fn lint_story(file: &str, source: &str) -> (Option<StoryFile>, Vec<Lint>) {
+    let mut lints = Vec::new();
+    let mut lint = |node, problem| {
+        lints.push(Lint {
+            file: file.to_string(),
+            node,
+            problem,
+        })
+    };
+    let story: StoryFile = match ron::from_str(source) {
+        Ok(story) => story,
+        Err(error) => {
+            lint(None, Problem::Invalid(StoryError::Parse(error)));
+            return (None, lints);
+        }
+    };
+    let context = story.initial_context();
+    let palette = story.palette().unwrap_or_else(|error| {
+        lint(None, Problem::Invalid(error));
+        Palette::default()
+    });
+
+    let mut times_written: BTreeMap<usize, usize> = BTreeMap::new();
+    for (index, node) in story.nodes.iter() {
+        *times_written.entry(*index).or_default() += 1;
+        match story::check_node(*index, node, &context, &palette) {
+            // Every markup mistake is found below, not just the first one.
+            Ok(()) | Err(StoryError::Markup { .. }) => {}
+            Err(error) => lint(Some(*index), Problem::Invalid(error)),
+        }
+
+        let content = match node {
+            Node::Simple { content, .. } => content,
+            Node::Fork { content, choices } => {
+                if choices.len() == 1 {
+                    lint(Some(*index), Problem::OneChoice);
+                }
+                for (position, choice) in choices.iter().enumerate() {
+                    if is_empty(&choice.text) {
+                        lint(
+                            Some(*index),
+                            Problem::EmptyText {
+                                field: format!("text of choice {position}"),
+                            },
+                        );
+                    }
+                    if choice.illustration.is_none() {
+                        lint(Some(*index), Problem::NoIllustration { choice: position });
+                    }
+                }
+                content
+            }
+        };
+        if is_empty(&content.text) {
+            lint(
+                Some(*index),
+                Problem::EmptyText {
+                    field: "text".to_string(),
+                },
+            );
+        }
+    }
+    for (index, times) in times_written {
+        if times > 1 {
+            lint(Some(index), Problem::DuplicateNode);
+        }
+    }
+
+    match localization::story_ftl(&story) {
+        Ok(resource) => {
+            let messages = localization::messages(Language::DEFAULT, Arc::new(resource), &context);
+            for (node, problem) in lint_messages(messages, &palette) {
+                lint(node, problem);
+            }
+        }
+        Err(error) => lint(
+            None,
+            Problem::Format {
+                key: "story".to_string(),
+                error: error.to_string(),
+            },
+        ),
+    }
+
+    let graph = story.to_graph();
+    for problem in graph.validate() {
+        let node = match problem {
+            GraphProblem::MissingStart => None,
+            GraphProblem::MissingNext { node, .. }
+            | GraphProblem::MissingChoiceNext { node, .. }
+            | GraphProblem::ChoiceFailed { node, .. }
+            | GraphProblem::Unreachable { node } => Some(node),
+        };
+        lint(node, Problem::Graph(problem));
+    }
+    let exploration = graph.explore();
+    let mut stuck = BTreeSet::new();
+    for playthrough in exploration.broken() {
+        if let PlaythroughEnd::DeadEnd(node) = playthrough.end {
+            stuck.insert(node);
+        }
+    }
+    // Going backwards from the endings.
+    let edges = exploration.edges();
+    let mut reach_an_ending = exploration.endings();
+    loop {
+        let before = reach_an_ending.len();
+        for edge in edges.iter() {
+            if reach_an_ending.contains(&edge.to) {
+                reach_an_ending.insert(edge.from);
+            }
+        }
+        if reach_an_ending.len() == before {
+            break;
+        }
+    }
+    let visited: BTreeSet<usize> = exploration.visited().into_iter().collect();
+    for node in stuck.iter() {
+        lint(Some(*node), Problem::Stuck);
+    }
+    for node in visited.difference(&reach_an_ending) {
+        if !stuck.contains(node) {
+            lint(Some(*node), Problem::NoEnding);
+        }
+    }
+
+    (Some(story), lints)
+}
+
+/// Lints the `.ftl` file of a language against the story it translates.
+pub fn lint_translation(
+    file: &str,
+    source: &str,
+    language: Language,
+    story: &StoryFile,
+) -> Vec<Lint> {
+    let lint = |node, problem| Lint {
+        file: file.to_string(),
+        node,
+        problem,
+    };
+    let resource = match localization::parse_ftl(source) {
+        Ok(resource) => resource,
+        Err(error) => return vec![lint(None, Problem::Unreadable(error.to_string()))],
+    };
+    let palette = story.palette().unwrap_or_default();
+    let messages = localization::messages(language, Arc::new(resource), &story.initial_context());
+    lint_messages(messages, &palette)
+        .into_iter()
+        .map(|(node, problem)| lint(node, problem))
+        .collect()
+}
+
+/// Markup, formatting and length of the texts of the story, as they are shown.
+fn lint_messages(messages: Vec<Message>, palette: &Palette) -> Vec<(Option<usize>, Problem)> {
+    let mut problems = Vec::new();
+    for Message { key, text, errors } in messages {
+        // Only the texts of the story use markup, the interface doesn't.
+        let Some((node, field)) = key
+            .strip_prefix("node-")
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(node, field)| Some((node.parse::<usize>().ok()?, field.to_string())))
+        else {
+            continue;
+        };
+        for error in errors {
+            problems.push((
+                Some(node),
+                Problem::Format {
+                    key: key.clone(),
+                    error,
+                },
+            ));
+        }
+        if let Err(error) = markup::parse(&text, palette) {
+            problems.push((
+                Some(node),
+                Problem::Markup {
+                    key: key.clone(),
+                    error,
+                },
+            ));
+        }
+        // The main text of a page, possibly one of its cases.
+        let is_page = field == "text"
+            || field
+                .strip_prefix("text-")
+                .is_some_and(|case| case.parse::<usize>().is_ok());
+        let characters = markup::plain_text(&text).chars().count();
+        if is_page && characters > MAX_PAGE_CHARACTERS {
+            problems.push((Some(node), Problem::TooLong { key, characters }));
+        }
+    }
+    problems
+}
+
+/// Empty whichever case is used.
+fn is_empty(text: &Text) -> bool {
+    text.strings().iter().all(|text| text.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mistakes_are_found_where_they_are() {
+        let source = r#"(
+            nodes: [
+                (0, Fork(
+                    content: (text: "Un *dragón"),
+                    choices: [
+                        (text: "Huir", illustration: Some("huir"), next: 1),
+                        (text: "", next: 2),
+                    ],
+                )),
+                (1, Fork(
+                    content: (text: "Solo queda luchar"),
+                    choices: [(text: "Luchar", illustration: Some("luchar"), next: 0)],
+                )),
+                (2, Simple(content: (text: "Fin"), next: None)),
+                (2, Simple(content: (text: "Fin"), next: None)),
+            ],
+        )"#;
+        let (story, lints) = lint_story("book.story.ron", source);
+        let story = story.unwrap();
+        let found: Vec<String> = lints.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            vec![
+                "book.story.ron: node 0: the text of choice 1 is empty",
+                "book.story.ron: node 0: choice 1 has no illustration",
+                "book.story.ron: node 1: it's a fork with only one choice",
+                "book.story.ron: node 2: written more than once, the story won't load",
+                "book.story.ron: node 0: `node-0-text` at character 3: `*` is never closed, \
+                 write `\\*` for an asterisk",
+            ]
+        );
+
+        let lints = lint_translation(
+            "ca/book.ftl",
+            "node-0-text = Un drac\nnode-1-text = Només [green]queda lluitar\n",
+            Language::Catalan,
+            &story,
+        );
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].node, Some(1));
+        assert!(matches!(lints[0].problem, Problem::Markup { .. }));
+
+        let source = source.replace(r#"(text: "Luchar", illustration: Some("luchar"), next: 0)"#, r#"(text: "Luchar", illustration: Some("luchar"), next: 1), (text: "Esperar", illustration: Some("esperar"), next: 1, availability: Hidden("false"))"#);
+        let (_, lints) = lint_story("book.story.ron", &source);
+        assert!(lints
+            .iter()
+            .any(|lint| lint.node == Some(1) && matches!(lint.problem, Problem::NoEnding)));
+
+        let source = r#"(nodes: [(0, Simple(
+            content: (text: "Fin", highlighted_color: Some("rojo")),
+            next: None,
+        ))])"#;
+        let (_, lints) = lint_story("book.story.ron", source);
+        let found: Vec<String> = lints.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            vec!["book.story.ron: node 0: uses `rojo`, which isn't a colour"]
+        );
+    }
+
+    #[test]
+    fn the_book_has_no_failing_lints() {
+        let (story, lints) = lint_story(
+            "book.story.ron",
+            include_str!("../assets/story/book.story.ron"),
+        );
+        assert!(story.is_some());
+        let failing: Vec<String> = lints
+            .iter()
+            .filter(|lint| !lint.problem.is_warning())
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(failing, Vec::<String>::new());
+    }
+}
@@ -1,3 +1,6 @@
+use crate::localization::{self, FluentFile, FluentLoader};
+use crate::menu::Language;
+use crate::story::{Story, StoryLoader};
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
@@ -10,23 +13,29 @@ pub struct LoadingPlugin;
 /// If interested, take a look at <https://bevy-cheatbook.github.io/features/assets.html>
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_loading_state(
-            LoadingState::new(GameState::Loading)
-                .continue_to_state(GameState::Menu)
-                .load_collection::<FontAssets>()
-                .load_collection::<ModelAssets>()
-                .load_collection::<AnimationAssets>()
-                .load_collection::<AudioAssets>()
-                .load_collection::<Illustrations>()
-                .load_collection::<UiTextures>(),
-        );
+        app.init_asset::<Story>()
+            .init_asset_loader::<StoryLoader>()
+            .init_asset::<FluentFile>()
+            .init_asset_loader::<FluentLoader>()
+            .add_loading_state(
+                LoadingState::new(GameState::Loading)
+                    .continue_to_state(GameState::Menu)
+                    .load_collection::<FontAssets>()
+                    .load_collection::<ModelAssets>()
+                    .load_collection::<AnimationAssets>()
+                    .load_collection::<AudioAssets>()
+                    .load_collection::<StoryAssets>()
+                    .load_collection::<LocaleAssets>()
+                    .load_collection::<UiTextures>(),
+            )
+            .add_systems(OnExit(GameState::Loading), localization::build_locales);
     }
 }
 
 // the following asset collections will be loaded during the State `GameState::Loading`
 // when done loading, they will be inserted as resources (see <https://github.com/NiklasEi/bevy_asset_loader>)
 
-#[derive(AssetCollection, Resource)]
+#[derive(AssetCollection, Resource, Default)]
 pub struct FontAssets {
     #[asset(path = "fonts/Seagram tfb.ttf")]
     pub normal: Handle<Font>,
@@ -36,8 +45,6 @@ pub struct FontAssets {
 
 #[derive(AssetCollection, Resource)]
 pub struct AudioAssets {
-    #[asset(path = "audio/flying.ogg")]
-    pub flying: Handle<AudioSource>,
     #[asset(path = "audio/background-music.ogg")]
     pub background_music: Handle<AudioSource>,
     #[asset(path = "audio/page-flip.ogg")]
@@ -46,7 +53,7 @@ pub struct AudioAssets {
     pub scribble: Handle<AudioSource>,
 }
 
-#[derive(AssetCollection, Resource)]
+#[derive(AssetCollection, Resource, Default)]
 pub struct UiTextures {
     #[asset(path = "textures/bevy.png")]
     pub bevy: Handle<Image>,
@@ -80,70 +87,38 @@ pub struct UiTextures {
     pub arrow: Handle<Image>,
     #[asset(path = "textures/flower-orange.png")]
     pub flower_orange: Handle<Image>,
-    #[asset(path = "textures/green-fancy-underline.png")]
-    pub green_fancy_underline: Handle<Image>,
     #[asset(path = "textures/cover.png")]
     pub cover: Handle<Image>,
     #[asset(path = "textures/choice-frame.png")]
     pub choice_frame: Handle<Image>,
 }
 
+/// The illustrations and decorations of the story are loaded along with it.
+#[derive(AssetCollection, Resource)]
+pub struct StoryAssets {
+    #[asset(path = "story/book.story.ron")]
+    pub book: Handle<Story>,
+}
+
+/// The story is written in Spanish, so that language only needs the texts of the interface.
 #[derive(AssetCollection, Resource)]
-pub struct Illustrations {
-    #[asset(path = "illustrations/normal-dragon.png")]
-    pub normal_dragon: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-disguised-as-dragon.png")]
-    pub sant_jordi_disguised_as_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-go-kill-dragon.png")]
-    pub princess_go_kill_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-excited-to-be-picked.png")]
-    pub princess_excited_to_be_picked: Handle<Image>,
-    #[asset(path = "illustrations/dragon-with-cow.png")]
-    pub dragon_with_cow: Handle<Image>,
-    #[asset(path = "illustrations/jordi-dragon-with-cow.png")]
-    pub jordi_dragon_with_cow: Handle<Image>,
-    #[asset(path = "illustrations/king-picks-princess.png")]
-    pub king_picks_princess: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-making-marshmallows.png")]
-    pub sant_jordi_making_marshmallows: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-warrior.png")]
-    pub sant_jordi_warrior: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-roses.png")]
-    pub sant_jordi_roses: Handle<Image>,
-    #[asset(path = "illustrations/princess-punches-jordi-dragon.png")]
-    pub princess_punches_jordi_dragon: Handle<Image>,
-    #[asset(path = "illustrations/jordi-dragon-confesses.png")]
-    pub jordi_dragon_confesses: Handle<Image>,
-    #[asset(path = "illustrations/princess-x-dragon.png")]
-    pub princess_x_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-analyzing-jordi-dragon.png")]
-    pub princess_analyzing_jordi_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-unmasks-jordi-dragon.png")]
-    pub princess_unmasks_jordi_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-leaves-with-dragon.png")]
-    pub dragon_returns_from_holidays: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-fighting-alone.png")]
-    pub sant_jordi_fighting_alone: Handle<Image>,
-    #[asset(path = "illustrations/sant-jordi-with-dragon-head.png")]
-    pub sant_jordi_with_dragon_head: Handle<Image>,
-    #[asset(path = "illustrations/sensual-dragon-coming-out-of-cave.png")]
-    pub sensual_dragon_coming_out_of_cave: Handle<Image>,
-    #[asset(path = "illustrations/jordi-dragon-accepts-princess.png")]
-    pub jordi_dragon_accepts_princess: Handle<Image>,
-    #[asset(path = "illustrations/princess-dragon.png")]
-    pub princess_dragon: Handle<Image>,
-    #[asset(path = "illustrations/dragon-x-jordi-dragon.png")]
-    pub dragon_x_jordi_dragon: Handle<Image>,
-    #[asset(path = "illustrations/jordi-dragon-rejects-princess.png")]
-    pub jordi_dragon_rejects_princess: Handle<Image>,
-    #[asset(path = "illustrations/dragon-chases-jordi-dragon.png")]
-    pub dragon_chases_jordi_dragon: Handle<Image>,
-    #[asset(path = "illustrations/princess-thinking.png")]
-    pub princess_thinking: Handle<Image>,
-    #[asset(path = "illustrations/dragon-x-sant-jordi.png")]
-    pub dragon_x_sant_jordi: Handle<Image>,
-    #[asset(path = "illustrations/dragon-and-jordi-dragon-go-to-scotland.png")]
-    pub dragon_and_jordi_dragon_go_to_scotland: Handle<Image>,
+pub struct LocaleAssets {
+    #[asset(path = "locales/ca/book.ftl")]
+    pub catalan_book: Handle<FluentFile>,
+    #[asset(path = "locales/ca/ui.ftl")]
+    pub catalan_ui: Handle<FluentFile>,
+    #[asset(path = "locales/es/ui.ftl")]
+    pub spanish_ui: Handle<FluentFile>,
+}
+
+impl LocaleAssets {
+    pub fn files(&self) -> [(Language, &Handle<FluentFile>); 3] {
+        [
+            (Language::Catalan, &self.catalan_book),
+            (Language::Catalan, &self.catalan_ui),
+            (Language::Spanish, &self.spanish_ui),
+        ]
+    }
 }
 
 #[derive(AssetCollection, Resource)]
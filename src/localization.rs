@@ -0,0 +1,598 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    log,
+    prelude::*,
+    utils::{BoxedFuture, HashMap},
+};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use fluent_syntax::{ast, parser::ParserError};
+
+use crate::{
+    book_content::{Availability, BookContext, Text},
+    graph::Node,
+    loading::{LocaleAssets, StoryAssets},
+    menu::Language,
+    story::{Story, StoryFile},
+    variables::Variable,
+};
+
+/// A `.ftl` file from `assets/locales/<language>`.
+#[derive(Asset, TypePath)]
+pub struct FluentFile(pub Arc<FluentResource>);
+
+#[derive(Debug)]
+pub enum LocalizationError {
+    Io(io::Error),
+    Parse(Vec<ParserError>),
+}
+
+impl fmt::Display for LocalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalizationError::Io(error) => write!(f, "couldn't read the translations: {error}"),
+            LocalizationError::Parse(errors) => {
+                write!(f, "couldn't parse the translations:")?;
+                for error in errors {
+                    write!(f, " {error} at {:?};", error.pos)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LocalizationError {}
+
+pub fn parse_ftl(source: &str) -> Result<FluentResource, LocalizationError> {
+    FluentResource::try_new(source.to_string())
+        .map_err(|(_, errors)| LocalizationError::Parse(errors))
+}
+
+/// Every `.ftl` file of the folder of each language in `locales`, for the tools.
+pub fn read_translations(
+    locales: &Path,
+) -> Result<Vec<(Language, FluentResource)>, LocalizationError> {
+    let mut translations = Vec::new();
+    for language in Language::ALL {
+        let folder = locales.join(language.code());
+        for path in ftl_files(&folder).map_err(LocalizationError::Io)? {
+            let source = fs::read_to_string(&path).map_err(LocalizationError::Io)?;
+            translations.push((language, parse_ftl(&source)?));
+        }
+    }
+    Ok(translations)
+}
+
+/// The `.ftl` files in the folder, in order. None if there's no such folder.
+pub fn ftl_files(folder: &Path) -> io::Result<Vec<PathBuf>> {
+    if !folder.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "ftl") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Keys of the texts of the story are the node, then the choice if any, then the field,
+/// like `node-3-choice-0-text`. Texts with cases have a key per case, with its position
+/// at the end, like `node-12-additional-text-1`.
+pub fn node_key(node: usize, field: &str) -> String {
+    format!("node-{node}-{field}")
+}
+
+pub fn choice_key(node: usize, choice: usize, field: &str) -> String {
+    format!("node-{node}-choice-{choice}-{field}")
+}
+
+/// Every text of the story along with its key, in the language it's written in.
+pub fn source_texts(story: &StoryFile) -> Vec<(String, &str)> {
+    fn add<'a>(texts: &mut Vec<(String, &'a str)>, key: String, text: &'a Text) {
+        // Fluent messages can't be empty, and empty texts are never looked up anyway.
+        match text {
+            Text::Plain(text) if text.trim().is_empty() => {}
+            Text::Plain(text) => texts.push((key, text.as_str())),
+            Text::Cases(cases) => {
+                for (position, case) in cases.iter().enumerate() {
+                    if !case.then.trim().is_empty() {
+                        texts.push((format!("{key}-{position}"), case.then.as_str()));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut texts = Vec::new();
+    for (index, node) in story.nodes.iter() {
+        match node {
+            Node::Simple { content, extra, .. } => {
+                add(&mut texts, node_key(*index, "text"), &content.text);
+                add(
+                    &mut texts,
+                    node_key(*index, "additional-text"),
+                    &extra.additional_text,
+                );
+            }
+            Node::Fork { content, choices } => {
+                add(&mut texts, node_key(*index, "text"), &content.text);
+                for (position, choice) in choices.iter().enumerate() {
+                    add(
+                        &mut texts,
+                        choice_key(*index, position, "text"),
+                        &choice.text,
+                    );
+                    add(
+                        &mut texts,
+                        choice_key(*index, position, "additional-text"),
+                        &choice.additional_text,
+                    );
+                    if let Availability::Locked { reason, .. } = &choice.availability {
+                        add(
+                            &mut texts,
+                            choice_key(*index, position, "locked-reason"),
+                            reason,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    texts
+}
+
+/// The texts of the story as Fluent messages, so they can use the variables of the story
+/// like the translations do, as in `{ $princesa }`.
+pub fn story_ftl(story: &StoryFile) -> Result<FluentResource, LocalizationError> {
+    let source: String = source_texts(story)
+        .into_iter()
+        .map(|(key, text)| format!("{key} = {}\n", ftl_value(text)))
+        .collect();
+    parse_ftl(&source)
+}
+
+/// Indents the lines after the first one, so they belong to the message.
+/// Lines starting with `[`, `*` or `.` would be read as variants or attributes,
+/// so those characters are written as string literals instead.
+fn ftl_value(text: &str) -> String {
+    let mut lines = text.split('\n');
+    let mut value = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        value.push_str("\n    ");
+        match line.chars().next() {
+            Some(first @ ('[' | '*' | '.')) => {
+                value.push_str(&format!("{{\"{first}\"}}{}", &line[1..]));
+            }
+            _ => value.push_str(line),
+        }
+    }
+    value
+}
+
+/// The variables of the story, as arguments of the messages.
+/// Booleans are `"true"` or `"false"`, so they can be used in selectors.
+fn arguments(context: &BookContext) -> FluentArgs {
+    let mut arguments = FluentArgs::new();
+    for (name, variable) in context.iter() {
+        let value: FluentValue = match variable {
+            Variable::Bool(value) => value.to_string().into(),
+            Variable::Int(value) => (*value).into(),
+            Variable::Text(value) => value.as_str().into(),
+            Variable::Counter(value) => (*value).into(),
+        };
+        arguments.set(name, value);
+    }
+    arguments
+}
+
+fn bundle(language: Language) -> FluentBundle<Arc<FluentResource>> {
+    let mut bundle = FluentBundle::new_concurrent(vec![language
+        .code()
+        .parse()
+        .expect("Language codes are valid")]);
+    // The marks around arguments are shown as boxes with our fonts.
+    bundle.set_use_isolating(false);
+    bundle
+}
+
+/// A message of a `.ftl` file as it's shown, for the tools.
+pub struct Message {
+    pub key: String,
+    pub text: String,
+    /// Like variables the story doesn't have.
+    pub errors: Vec<String>,
+}
+
+/// Every message of the resource, with the variables of the context.
+pub fn messages(
+    language: Language,
+    resource: Arc<FluentResource>,
+    context: &BookContext,
+) -> Vec<Message> {
+    let mut bundle = bundle(language);
+    // Messages written twice are left for whoever reads the file to find.
+    let _ = bundle.add_resource(resource.clone());
+    let arguments = arguments(context);
+    let mut messages = Vec::new();
+    for entry in resource.entries() {
+        let ast::Entry::Message(message) = entry else {
+            continue;
+        };
+        let key = message.id.name;
+        let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let text = bundle
+            .format_pattern(pattern, Some(&arguments), &mut errors)
+            .into_owned();
+        messages.push(Message {
+            key: key.to_string(),
+            text,
+            errors: errors.iter().map(ToString::to_string).collect(),
+        });
+    }
+    messages
+}
+
+/// The messages of every language. Those missing from one language are shown in
+/// [`Language::DEFAULT`], which has the texts of the story itself.
+#[derive(Resource)]
+pub struct Locales {
+    bundles: HashMap<Language, FluentBundle<Arc<FluentResource>>>,
+    keys: HashMap<Language, BTreeSet<String>>,
+}
+
+impl Locales {
+    pub fn new(
+        story: Arc<FluentResource>,
+        files: impl IntoIterator<Item = (Language, Arc<FluentResource>)>,
+    ) -> Self {
+        let mut locales = Self {
+            bundles: HashMap::default(),
+            keys: HashMap::default(),
+        };
+        for language in Language::ALL {
+            locales.bundles.insert(language, bundle(language));
+            locales.keys.insert(language, BTreeSet::new());
+        }
+        locales.add(Language::DEFAULT, story);
+        for (language, resource) in files {
+            locales.add(language, resource);
+        }
+        locales
+    }
+
+    fn add(&mut self, language: Language, resource: Arc<FluentResource>) {
+        let keys = self
+            .keys
+            .get_mut(&language)
+            .expect("Every language has keys");
+        for entry in resource.entries() {
+            if let ast::Entry::Message(message) = entry {
+                keys.insert(message.id.name.to_string());
+            }
+        }
+        let bundle = self
+            .bundles
+            .get_mut(&language)
+            .expect("Every language has a bundle");
+        if let Err(errors) = bundle.add_resource(resource) {
+            for error in errors {
+                log::warn!("{language:?} translations: {error}");
+            }
+        }
+    }
+
+    /// Keys of the default language the given one doesn't have.
+    pub fn missing_keys(&self, language: Language) -> Vec<&str> {
+        self.keys[&Language::DEFAULT]
+            .difference(&self.keys[&language])
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Keys of the given language the default one doesn't have, probably old ones.
+    pub fn unknown_keys(&self, language: Language) -> Vec<&str> {
+        self.keys[&language]
+            .difference(&self.keys[&Language::DEFAULT])
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn format(
+        &self,
+        language: Language,
+        key: &str,
+        arguments: Option<&FluentArgs>,
+    ) -> Option<String> {
+        let (bundle, message) = [language, Language::DEFAULT]
+            .into_iter()
+            .map(|language| &self.bundles[&language])
+            .find_map(|bundle| Some((bundle, bundle.get_message(key)?)))?;
+        let mut errors = Vec::new();
+        let text = bundle
+            .format_pattern(message.value()?, arguments, &mut errors)
+            .into_owned();
+        for error in errors {
+            log::warn!("Couldn't format `{key}`: {error}");
+        }
+        Some(text)
+    }
+
+    /// The text of the story at `key`, for the case that holds if it has cases.
+    pub fn text<'a>(
+        &self,
+        language: Language,
+        key: &str,
+        text: &'a Text,
+        context: &BookContext,
+    ) -> Cow<'a, str> {
+        let (case, source) = text.get_case(context);
+        if source.is_empty() {
+            return Cow::Borrowed("");
+        }
+        let key = match case {
+            Some(case) => format!("{key}-{case}"),
+            None => key.to_string(),
+        };
+        self.format(language, &key, Some(&arguments(context)))
+            .map_or(Cow::Borrowed(source), Cow::Owned)
+    }
+
+    /// A text of the interface, from the `ui.ftl` files. The key itself if it's nowhere.
+    pub fn ui(&self, language: Language, key: &str) -> String {
+        self.format(language, key, None).unwrap_or_else(|| {
+            log::warn!("There's no `{key}` text");
+            key.to_string()
+        })
+    }
+}
+
+/// Puts together the story and the translations once they are loaded,
+/// warning about the texts that aren't translated.
+pub fn build_locales(
+    mut commands: Commands,
+    stories: Res<Assets<Story>>,
+    story_assets: Res<StoryAssets>,
+    files: Res<Assets<FluentFile>>,
+    locale_assets: Res<LocaleAssets>,
+) {
+    let story = stories
+        .get(&story_assets.book)
+        .expect("The story is loaded");
+    let story = story_ftl(&story.file).expect("Checked when the story was loaded");
+    let locales = Locales::new(
+        Arc::new(story),
+        locale_assets
+            .files()
+            .into_iter()
+            .filter_map(|(language, handle)| Some((language, files.get(handle)?.0.clone()))),
+    );
+    for language in Language::ALL {
+        let missing = locales.missing_keys(language);
+        if !missing.is_empty() {
+            log::warn!(
+                "{} texts aren't translated to {language:?}, they'll be shown in {:?}: {}",
+                missing.len(),
+                Language::DEFAULT,
+                missing.join(", ")
+            );
+        }
+        let unknown = locales.unknown_keys(language);
+        if !unknown.is_empty() {
+            log::warn!(
+                "{language:?} translates texts that don't exist: {}",
+                unknown.join(", ")
+            );
+        }
+    }
+    commands.insert_resource(locales);
+}
+
+/// Looks texts up in the [`Locales`] for the active [`Language`].
+#[derive(SystemParam)]
+pub struct Localizer<'w> {
+    language: Res<'w, Language>,
+    locales: Res<'w, Locales>,
+}
+
+impl<'w> Localizer<'w> {
+    pub fn language(&self) -> Language {
+        *self.language
+    }
+
+    pub fn text<'a>(&self, key: &str, text: &'a Text, context: &BookContext) -> Cow<'a, str> {
+        self.locales.text(*self.language, key, text, context)
+    }
+
+    pub fn ui(&self, key: &str) -> String {
+        self.locales.ui(*self.language, key)
+    }
+}
+
+#[derive(Default)]
+pub struct FluentLoader;
+
+impl AssetLoader for FluentLoader {
+    type Asset = FluentFile;
+    type Settings = ();
+    type Error = LocalizationError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<FluentFile, LocalizationError>> {
+        Box::pin(async move {
+            let mut source = String::new();
+            reader
+                .read_to_string(&mut source)
+                .await
+                .map_err(LocalizationError::Io)?;
+            Ok(FluentFile(Arc::new(parse_ftl(&source)?)))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{markup, variables::VariableStore};
+
+    fn ftl(source: &str) -> Arc<FluentResource> {
+        Arc::new(parse_ftl(source).unwrap())
+    }
+
+    #[test]
+    fn catalan_translates_everything() {
+        let story = StoryFile::from_ron(include_str!("../assets/story/book.story.ron")).unwrap();
+        let locales = Locales::new(
+            Arc::new(story_ftl(&story).unwrap()),
+            [
+                (
+                    Language::Catalan,
+                    ftl(include_str!("../assets/locales/ca/book.ftl")),
+                ),
+                (
+                    Language::Catalan,
+                    ftl(include_str!("../assets/locales/ca/ui.ftl")),
+                ),
+                (
+                    Language::Spanish,
+                    ftl(include_str!("../assets/locales/es/ui.ftl")),
+                ),
+            ],
+        );
+        assert_eq!(locales.missing_keys(Language::Catalan), Vec::<&str>::new());
+        assert_eq!(locales.unknown_keys(Language::Catalan), Vec::<&str>::new());
+        let context = story.initial_context();
+        let arguments = arguments(&context);
+        let palette = story.palette().unwrap();
+        // The interface doesn't use markup.
+        for key in locales.keys[&Language::Catalan]
+            .iter()
+            .filter(|key| key.starts_with("node-"))
+        {
+            let text = locales
+                .format(Language::Catalan, key, Some(&arguments))
+                .unwrap();
+            if let Err(error) = markup::parse(&text, &palette) {
+                panic!("`{key}` {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn messages_use_the_variables_of_the_story() {
+        let locales = Locales::new(
+            ftl("saludo = Hola, { $princesa }\n"),
+            [(
+                Language::Catalan,
+                ftl(r#"
+saludo = Hola, { $princesa }
+visitas = { $visitas ->
+    [one] Hi has vingut una vegada
+   *[other] Hi has vingut { $visitas } vegades
+}
+drac = { $genero ->
+    [femenino] La dragona
+   *[masculino] El drac
+} { $enfadado ->
+    [true] treu foc
+   *[false] dorm
+}
+"#),
+            )],
+        );
+        let mut context = VariableStore::new(BTreeMap::from([
+            (
+                "princesa".to_string(),
+                Variable::Text("Cleodolinda".to_string()),
+            ),
+            ("visitas".to_string(), Variable::Counter(1)),
+            ("genero".to_string(), Variable::Text("femenino".to_string())),
+            ("enfadado".to_string(), Variable::Bool(false)),
+        ]));
+        let text = |key: &str, context: &BookContext| {
+            locales
+                .text(
+                    Language::Catalan,
+                    key,
+                    &Text::Plain("...".to_string()),
+                    context,
+                )
+                .into_owned()
+        };
+        assert_eq!(text("saludo", &context), "Hola, Cleodolinda");
+        assert_eq!(text("visitas", &context), "Hi has vingut una vegada");
+        assert_eq!(text("drac", &context), "La dragona dorm");
+        context.set_counter("visitas", 3).unwrap();
+        context.set_text("genero", "masculino").unwrap();
+        context.set_bool("enfadado", true).unwrap();
+        assert_eq!(text("visitas", &context), "Hi has vingut 3 vegades");
+        assert_eq!(text("drac", &context), "El drac treu foc");
+    }
+
+    #[test]
+    fn story_texts_are_valid_messages() {
+        let story = StoryFile::from_ron(
+            r#"(
+                variables: {"princesa": Text("Cleodolinda")},
+                nodes: [(0, Simple(
+                    content: (text: "Al final:\n[3d793a]{ $princesa }[/]\n*Fin*\n..."),
+                    extra: (additional_text: [(when: "true", then: ""), (when: "false", then: "Otro")]),
+                    next: None,
+                ))],
+            )"#,
+        )
+        .unwrap();
+        let locales = Locales::new(Arc::new(story_ftl(&story).unwrap()), []);
+        let context = story.initial_context();
+        assert_eq!(
+            locales
+                .format(Language::Spanish, "node-0-text", Some(&arguments(&context)))
+                .unwrap(),
+            "Al final:\n[3d793a]Cleodolinda[/]\n*Fin*\n..."
+        );
+        assert!(locales
+            .format(Language::Spanish, "node-0-additional-text-0", None)
+            .is_none());
+        assert_eq!(
+            locales.format(Language::Spanish, "node-0-additional-text-1", None),
+            Some("Otro".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_texts_fall_back_to_the_default_language() {
+        let locales = Locales::new(
+            ftl("saludo = Hola\ndespedida = Adiós\n"),
+            [(Language::Catalan, ftl("saludo = Hola!\n"))],
+        );
+        assert_eq!(locales.missing_keys(Language::Catalan), vec!["despedida"]);
+        assert_eq!(locales.ui(Language::Catalan, "despedida"), "Adiós");
+        assert_eq!(locales.ui(Language::Catalan, "saludo"), "Hola!");
+        assert_eq!(locales.ui(Language::Spanish, "nada"), "nada");
+    }
+}
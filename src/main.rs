@@ -6,6 +6,7 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use bevy::winit::WinitWindows;
 use bevy::DefaultPlugins;
+use sant_jordi::settings::Settings;
 use sant_jordi::GamePlugin;
 use std::io::Cursor;
 use winit::window::Icon;
@@ -22,7 +23,7 @@ fn main() {
                 canvas: Some("#bevy".to_owned()),
                 // Tells wasm not to override default event handling, like F5 and Ctrl+R
                 prevent_default_event_handling: false,
-                mode: bevy::window::WindowMode::BorderlessFullscreen,
+                mode: Settings::load().window_mode(),
                 ..default()
             }),
             ..default()
@@ -0,0 +1,274 @@
+//! The inline markup of the texts of the book:
+//! - `*highlighted*`, in the highlight colour of the page.
+//! - `_italics_`, in a lighter ink in the book, as its font has no italic. The gamebooks use real
+//!   italics.
+//! - `[green]coloured[/]`, with a named colour of the [`Palette`] or a hex colour like `[3d793a]`.
+//! - `\n` for a line break, and `\*`, `\_`, `\[`, `\]` and `\\` for the characters themselves.
+//!
+//! They can be nested, like `*a [green]green and _italic_[/] highlight*`.
+
+use std::{collections::BTreeMap, fmt};
+
+use bevy::{prelude::*, utils::HashMap};
+
+/// A piece of text written all in the same way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub highlighted: bool,
+    pub italic: bool,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupError {
+    /// In characters from the start of the text.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at character {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+fn error<T>(position: usize, message: impl Into<String>) -> Result<T, MarkupError> {
+    Err(MarkupError {
+        position,
+        message: message.into(),
+    })
+}
+
+/// Colours that can be used by name, the ones of the book along with the ones a story declares.
+#[derive(Resource, Clone, Debug)]
+pub struct Palette {
+    colors: HashMap<String, Color>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let colors = [
+            ("black", Color::BLACK),
+            ("white", Color::WHITE),
+            ("brown", Color::hex("3a1e0d").unwrap()),
+            ("red", Color::rgb(0.678, 0.047, 0.109)),
+            ("purple", Color::rgb(0.235, 0.039, 0.337)),
+            ("green", Color::hex("3d793a").unwrap()),
+            ("blue", Color::hex("282c83").unwrap()),
+            ("gold", Color::hex("b8860b").unwrap()),
+        ];
+        Self {
+            colors: colors
+                .into_iter()
+                .map(|(name, color)| (name.to_string(), color))
+                .collect(),
+        }
+    }
+}
+
+impl Palette {
+    /// Along with the given colours, as hex. Returns the name of the first one that isn't valid.
+    pub fn with_colors(colors: &BTreeMap<String, String>) -> Result<Self, String> {
+        let mut palette = Self::default();
+        for (name, hex) in colors.iter() {
+            let color = Color::hex(hex).map_err(|_| name.clone())?;
+            palette.colors.insert(name.clone(), color);
+        }
+        Ok(palette)
+    }
+
+    /// The colour with that name, or the colour itself if it's a hex colour.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors
+            .get(name)
+            .copied()
+            .or_else(|| Color::hex(name).ok())
+    }
+}
+
+pub fn parse(source: &str, palette: &Palette) -> Result<Vec<Span>, MarkupError> {
+    parse_with(source, |name| palette.get(name))
+}
+
+/// The text without the markup, or as it is if the markup is wrong.
+/// Any colour name is fine, since they aren't shown.
+pub fn plain_text(source: &str) -> String {
+    match parse_with(source, |_| Some(Color::NONE)) {
+        Ok(spans) => spans.into_iter().map(|span| span.text).collect(),
+        Err(_) => source.to_string(),
+    }
+}
+
+fn parse_with(
+    source: &str,
+    get_color: impl Fn(&str) -> Option<Color>,
+) -> Result<Vec<Span>, MarkupError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    // Where each style was opened, to point at it if it's never closed.
+    let mut highlighted: Option<usize> = None;
+    let mut italic: Option<usize> = None;
+    let mut colors: Vec<(Color, usize)> = Vec::new();
+    let mut position = 0;
+
+    let mut flush = |current: &mut String,
+                     highlighted: Option<usize>,
+                     italic: Option<usize>,
+                     colors: &[(Color, usize)]| {
+        if !current.is_empty() {
+            spans.push(Span {
+                text: std::mem::take(current),
+                highlighted: highlighted.is_some(),
+                italic: italic.is_some(),
+                color: colors.last().map(|(color, _)| *color),
+            });
+        }
+    };
+
+    while let Some(&c) = chars.get(position) {
+        match c {
+            '\\' => {
+                match chars.get(position + 1) {
+                    Some('n') => current.push('\n'),
+                    Some(&escaped @ ('*' | '_' | '[' | ']' | '\\')) => current.push(escaped),
+                    Some(other) => {
+                        return error(
+                            position,
+                            format!("`\\{other}` isn't an escape, write `\\\\` for a backslash"),
+                        )
+                    }
+                    None => return error(position, "the text ends with a `\\`"),
+                }
+                position += 2;
+                continue;
+            }
+            '*' => {
+                flush(&mut current, highlighted, italic, &colors);
+                highlighted = match highlighted {
+                    Some(_) => None,
+                    None => Some(position),
+                };
+            }
+            '_' => {
+                flush(&mut current, highlighted, italic, &colors);
+                italic = match italic {
+                    Some(_) => None,
+                    None => Some(position),
+                };
+            }
+            '[' => {
+                let Some(length) = chars[position + 1..].iter().position(|&c| c == ']') else {
+                    return error(position, "`[` is never closed, write `\\[` for a bracket");
+                };
+                let name: String = chars[position + 1..position + 1 + length].iter().collect();
+                flush(&mut current, highlighted, italic, &colors);
+                if name == "/" {
+                    if colors.pop().is_none() {
+                        return error(position, "`[/]` doesn't close any colour");
+                    }
+                } else {
+                    let Some(color) = get_color(&name) else {
+                        return error(position, format!("there's no colour called `{name}`"));
+                    };
+                    colors.push((color, position));
+                }
+                position += length + 2;
+                continue;
+            }
+            ']' => {
+                return error(
+                    position,
+                    "`]` doesn't close anything, write `\\]` for a bracket",
+                )
+            }
+            _ => current.push(c),
+        }
+        position += 1;
+    }
+    flush(&mut current, highlighted, italic, &colors);
+
+    if let Some(position) = highlighted {
+        return error(position, "`*` is never closed, write `\\*` for an asterisk");
+    }
+    if let Some(position) = italic {
+        return error(
+            position,
+            "`_` is never closed, write `\\_` for an underscore",
+        );
+    }
+    if let Some((_, position)) = colors.last() {
+        return error(*position, "the colour is never closed with `[/]`");
+    }
+    Ok(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str, highlighted: bool, italic: bool, color: Option<Color>) -> Span {
+        Span {
+            text: text.to_string(),
+            highlighted,
+            italic,
+            color,
+        }
+    }
+
+    #[test]
+    fn markup_is_parsed() {
+        let palette = Palette::default();
+        let parse = |source| parse(source, &palette).unwrap();
+        assert_eq!(
+            parse("Hello, how're you?"),
+            vec![span("Hello, how're you?", false, false, None)]
+        );
+        assert_eq!(
+            parse("This *text* has some *important* bits"),
+            vec![
+                span("This ", false, false, None),
+                span("text", true, false, None),
+                span(" has some ", false, false, None),
+                span("important", true, false, None),
+                span(" bits", false, false, None),
+            ]
+        );
+        let green = palette.get("green");
+        assert_eq!(
+            parse("*Sant [green]Jordi _y_[/] el* drac"),
+            vec![
+                span("Sant ", true, false, None),
+                span("Jordi ", true, false, green),
+                span("y", true, true, green),
+                span(" el", true, false, None),
+                span(" drac", false, false, None),
+            ]
+        );
+        assert_eq!(
+            parse(r"\*5\* \[ok\]\nA\\B"),
+            vec![span("*5* [ok]\nA\\B", false, false, None)]
+        );
+        assert_eq!(
+            parse("[ff0000]rojo[/]"),
+            vec![span("rojo", false, false, Color::hex("ff0000").ok())]
+        );
+    }
+
+    #[test]
+    fn mistakes_are_reported_where_they_are() {
+        let palette = Palette::default();
+        let position = |source| parse(source, &palette).unwrap_err().position;
+        assert_eq!(position("Un *terrible dragón"), 3);
+        assert_eq!(position("Un _terrible_ *dragón"), 14);
+        assert_eq!(position("Un [rosa]dragón[/]"), 3);
+        assert_eq!(position("Un [green]dragón"), 3);
+        assert_eq!(position("Un dragón[/]"), 9);
+        assert_eq!(position("Un [dragón"), 3);
+        assert_eq!(position("Un dragón]"), 9);
+        assert_eq!(position(r"Un \dragón"), 3);
+    }
+}
@@ -1,8 +1,14 @@
+#![allow(clippy::too_many_arguments)]
+
+use crate::actions::{Action, Actions};
 use crate::book::{BUTTON_HOVER_COLOR, BUTTON_NORMAL_COLOR};
-use crate::loading::{AudioAssets, FontAssets, ModelAssets, UiTextures};
+use crate::controls;
+use crate::loading::{FontAssets, ModelAssets, UiTextures};
+use crate::localization::Localizer;
+use crate::save::{self, SavedGame};
+use crate::settings::{self, Settings, SettingsButton};
 use crate::GameState;
 use bevy::prelude::*;
-use bevy_kira_audio::prelude::*;
 
 pub const MENU_BUTTON_RED: Color = Color::rgb(0.678, 0.047, 0.109);
 
@@ -13,48 +19,108 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Language::Catalan)
+            .init_resource::<MenuPage>()
+            .init_resource::<SavedGame>()
             .add_systems(
                 OnEnter(GameState::Menu),
                 (
-                    start_background_music,
+                    save::load_saved_game,
                     setup_book,
-                    setup_menu.after(setup_book),
+                    setup_menu.after(setup_book).after(save::load_saved_game),
                 ),
             )
             .add_systems(
                 Update,
-                (interact_with_language_buttons, click_play_button)
+                (
+                    interact_with_language_buttons,
+                    click_play_button,
+                    click_continue_button,
+                    click_settings_button,
+                    start_with_actions,
+                    // Everything in the menu is written in the language.
+                    (cleanup_menu, setup_menu).chain().run_if(
+                        resource_changed::<Language>
+                            .or_else(resource_changed::<MenuPage>)
+                            .or_else(resource_changed::<Settings>),
+                    ),
+                )
                     .run_if(in_state(GameState::Menu)),
             )
-            .add_systems(OnExit(GameState::Menu), cleanup_menu);
+            .add_systems(OnExit(GameState::Menu), cleanup_menu)
+            .add_systems(OnExit(GameState::Playing), cleanup_book);
     }
 }
 
-fn start_background_music(audio_assets: Res<AudioAssets>, audio: Res<Audio>) {
-    audio.play(audio_assets.background_music.clone()).looped();
-}
-
-#[derive(Resource, Clone)]
+/// The language the book is read in, see [`crate::localization`].
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Language {
     Catalan,
     Spanish,
 }
 
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Catalan, Language::Spanish];
+    /// The one the story is written in, missing translations are shown in it.
+    pub const DEFAULT: Language = Language::Spanish;
+
+    /// Also the name of its folder in `assets/locales`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Catalan => "ca",
+            Language::Spanish => "es",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+}
+
+/// What the second page of the menu book shows.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuPage {
+    #[default]
+    Main,
+    Settings,
+    Controls {
+        /// Waiting for the input to bind to it.
+        rebinding: Option<Action>,
+    },
+}
+
 #[derive(Component)]
 pub struct PickLanguage(pub Language);
 
 #[derive(Component)]
 pub struct PlayButton;
 
+#[derive(Component)]
+pub struct ContinueButton;
+
 #[derive(Component)]
 struct Menu;
 
+/// Everything making up the book, which stays from the menu until the story is left.
+#[derive(Component)]
+pub struct BookScene;
+
 #[derive(Component)]
 pub struct FirstPage;
 
 #[derive(Component)]
 pub struct SecondPage;
 
+/// Underlines the language being used.
+fn language_border_color(language: Language, picked: Language) -> BorderColor {
+    if language == picked {
+        MENU_BUTTON_RED.into()
+    } else {
+        Color::NONE.into()
+    }
+}
+
 fn interact_with_language_buttons(
     mut interaction_query: Query<(&Interaction, &PickLanguage), Changed<Interaction>>,
     mut language: ResMut<Language>,
@@ -62,7 +128,7 @@ fn interact_with_language_buttons(
     for (interaction, pick_language) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
-                *language = pick_language.0.clone();
+                *language = pick_language.0;
             }
             Interaction::None => {}
             _ => {}
@@ -71,40 +137,52 @@ fn interact_with_language_buttons(
 }
 
 fn setup_book(mut commands: Commands, models: Res<ModelAssets>) {
-    commands.spawn(Camera3dBundle {
-        transform: Transform::from_xyz(-0.1, 3.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
-        camera: Camera {
-            order: 1,
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(-0.1, 3.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
             ..default()
         },
-        ..default()
-    });
-    commands.spawn(SceneBundle {
-        scene: models.book.clone(),
-        ..default()
-    });
-    commands.spawn(DirectionalLightBundle {
-        directional_light: DirectionalLight {
-            shadows_enabled: true,
+        BookScene,
+    ));
+    commands.spawn((
+        SceneBundle {
+            scene: models.book.clone(),
             ..default()
         },
-        transform: Transform::from_xyz(3.0, 9.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..default()
-    });
-    commands
-        .spawn(NodeBundle {
-            style: Style {
-                display: Display::Flex,
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                width: Val::Percent(100.),
-                height: Val::Percent(100.),
-                // border: UiRect::all(Val::Px(2.)),
+        BookScene,
+    ));
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                shadows_enabled: true,
                 ..default()
             },
-            // border_color: Color::GREEN.into(),
+            transform: Transform::from_xyz(3.0, 9.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
             ..default()
-        })
+        },
+        BookScene,
+    ));
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    // border: UiRect::all(Val::Px(2.)),
+                    ..default()
+                },
+                // border_color: Color::GREEN.into(),
+                ..default()
+            },
+            BookScene,
+        ))
         .with_children(|parent| {
             parent
                 .spawn(NodeBundle {
@@ -174,7 +252,13 @@ fn setup_menu(
     second_page: Query<Entity, With<SecondPage>>,
     textures: Res<UiTextures>,
     fonts: Res<FontAssets>,
+    localizer: Localizer,
+    menu_page: Res<MenuPage>,
+    settings: Res<Settings>,
+    saved_game: Res<SavedGame>,
 ) {
+    let language = localizer.language();
+    let has_save = saved_game.0.is_some();
     let mut first_page = commands.entity(first_page.single());
     first_page.with_children(|parent| {
         parent.spawn((
@@ -209,73 +293,161 @@ fn setup_menu(
                 Menu,
             ))
             .with_children(|parent| {
-                // Play button.
+                match *menu_page {
+                    MenuPage::Main => {}
+                    MenuPage::Settings => {
+                        settings::spawn_settings_page(parent, &settings, &fonts, &localizer);
+                        return;
+                    }
+                    MenuPage::Controls { rebinding } => {
+                        controls::spawn_controls_page(
+                            parent,
+                            &settings.bindings,
+                            rebinding,
+                            &fonts,
+                            &localizer,
+                        );
+                        return;
+                    }
+                }
                 parent
-                    .spawn((ButtonBundle::default(), PlayButton))
-                    .with_children(|parent| {
-                        parent.spawn(ImageBundle {
-                            image: textures.play_button.clone().into(),
-                            style: Style {
-                                height: Val::Px(100.),
-                                ..default()
-                            },
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
                             ..default()
-                        });
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        // Play button.
+                        parent
+                            .spawn((ButtonBundle::default(), PlayButton))
+                            .with_children(|parent| {
+                                parent.spawn(ImageBundle {
+                                    image: textures.play_button.clone().into(),
+                                    style: Style {
+                                        height: Val::Px(100.),
+                                        ..default()
+                                    },
+                                    ..default()
+                                });
+                            });
+                        // Continue button, only if there's something to continue.
+                        if has_save {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        background_color: Color::NONE.into(),
+                                        style: Style {
+                                            margin: UiRect::left(Val::Px(20.)),
+                                            padding: UiRect::all(Val::Px(5.)),
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    ContinueButton,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        localizer.ui("menu-continue"),
+                                        TextStyle {
+                                            font: fonts.normal.clone(),
+                                            font_size: 30.,
+                                            color: MENU_BUTTON_RED,
+                                        },
+                                    ));
+                                });
+                        }
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    background_color: Color::NONE.into(),
+                                    style: Style {
+                                        margin: UiRect::left(Val::Px(20.)),
+                                        padding: UiRect::all(Val::Px(5.)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                SettingsButton,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    localizer.ui("menu-settings"),
+                                    TextStyle {
+                                        font: fonts.normal.clone(),
+                                        font_size: 30.,
+                                        color: MENU_BUTTON_RED,
+                                    },
+                                ));
+                            });
                     });
                 // Language buttons.
-                // parent
-                //     .spawn(NodeBundle {
-                //         style: Style {
-                //             display: Display::Flex,
-                //             justify_content: JustifyContent::SpaceAround,
-                //             ..default()
-                //         },
-                //         ..default()
-                //     })
-                //     .with_children(|parent| {
-                //         parent
-                //             .spawn((
-                //                 ButtonBundle {
-                //                     background_color: Color::NONE.into(),
-                //                     border_color: MENU_BUTTON_RED.into(),
-                //                     ..default()
-                //                 },
-                //                 PickLanguage(Language::Catalan),
-                //             ))
-                //             .with_children(|parent| {
-                //                 parent.spawn(TextBundle::from_section(
-                //                     "Català",
-                //                     TextStyle {
-                //                         font: fonts.normal.clone(),
-                //                         font_size: 30.,
-                //                         color: MENU_BUTTON_RED,
-                //                     },
-                //                 ));
-                //             });
-                //         parent
-                //             .spawn((
-                //                 ButtonBundle {
-                //                     background_color: Color::NONE.into(),
-                //                     border_color: MENU_BUTTON_RED.into(),
-                //                     style: Style {
-                //                         margin: UiRect::left(Val::Px(20.)),
-                //                         ..default()
-                //                     },
-                //                     ..default()
-                //                 },
-                //                 PickLanguage(Language::Spanish),
-                //             ))
-                //             .with_children(|parent| {
-                //                 parent.spawn(TextBundle::from_section(
-                //                     "Castellano",
-                //                     TextStyle {
-                //                         font: fonts.normal.clone(),
-                //                         font_size: 30.,
-                //                         color: MENU_BUTTON_RED,
-                //                     },
-                //                 ));
-                //             });
-                //     });
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            justify_content: JustifyContent::SpaceAround,
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    background_color: Color::NONE.into(),
+                                    border_color: language_border_color(
+                                        Language::Catalan,
+                                        language,
+                                    ),
+                                    style: Style {
+                                        border: UiRect::bottom(Val::Px(2.)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                PickLanguage(Language::Catalan),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    localizer.ui("language-catalan"),
+                                    TextStyle {
+                                        font: fonts.normal.clone(),
+                                        font_size: 30.,
+                                        color: MENU_BUTTON_RED,
+                                    },
+                                ));
+                            });
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    background_color: Color::NONE.into(),
+                                    border_color: language_border_color(
+                                        Language::Spanish,
+                                        language,
+                                    ),
+                                    style: Style {
+                                        margin: UiRect::left(Val::Px(20.)),
+                                        border: UiRect::bottom(Val::Px(2.)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                PickLanguage(Language::Spanish),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    localizer.ui("language-spanish"),
+                                    TextStyle {
+                                        font: fonts.normal.clone(),
+                                        font_size: 30.,
+                                        color: MENU_BUTTON_RED,
+                                    },
+                                ));
+                            });
+                    });
                 // Controls.
                 parent
                     .spawn(NodeBundle {
@@ -309,7 +481,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "Mouse",
+                                    localizer.ui("controls-mouse"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size: 20.,
@@ -340,7 +512,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "[Space bar]",
+                                    localizer.ui("controls-space-bar"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size: 20.,
@@ -389,7 +561,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "Pablo Ferrer",
+                                    localizer.ui("credits-pablo"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size,
@@ -420,7 +592,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "Álex Pérez",
+                                    localizer.ui("credits-alex"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size,
@@ -451,7 +623,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "Francisco Aguirre",
+                                    localizer.ui("credits-fran"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size,
@@ -482,7 +654,7 @@ fn setup_menu(
                                     ..default()
                                 });
                                 parent.spawn(TextBundle::from_section(
-                                    "Claudia Mohedano",
+                                    localizer.ui("credits-claudia"),
                                     TextStyle {
                                         font: fonts.normal.clone(),
                                         font_size,
@@ -517,8 +689,82 @@ fn click_play_button(
     }
 }
 
+fn click_continue_button(
+    mut commands: Commands,
+    mut saved_game: ResMut<SavedGame>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ContinueButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                if let Some(save) = saved_game.0.take() {
+                    commands.insert_resource(save);
+                }
+                next_state.set(GameState::Playing);
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Confirming goes on reading, from the saved game if there's one,
+/// so the book can be opened without a mouse.
+fn start_with_actions(
+    mut commands: Commands,
+    actions: Res<Actions>,
+    menu_page: Res<MenuPage>,
+    mut saved_game: ResMut<SavedGame>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Not with the menu action, which brought the reader here.
+    if *menu_page != MenuPage::Main || !actions.just_pressed(Action::Confirm) {
+        return;
+    }
+    if let Some(save) = saved_game.0.take() {
+        commands.insert_resource(save);
+    }
+    next_state.set(GameState::Playing);
+}
+
+fn click_settings_button(
+    mut menu_page: ResMut<MenuPage>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<SettingsButton>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *menu_page = MenuPage::Settings;
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
 fn cleanup_menu(mut commands: Commands, menu: Query<Entity, With<Menu>>) {
     for entity in menu.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
+
+fn cleanup_book(mut commands: Commands, book: Query<Entity, With<BookScene>>) {
+    for entity in book.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,70 @@
+//! The background music, in a channel of its own.
+//! The music is quieter while sound effects play, including the scribbling of the typewriter.
+//! Its volume is the one in the [`Settings`], set on the track since it's faded with it.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::{loading::AudioAssets, settings::Settings, GameState};
+
+/// How long it takes for the music to be ducked or to come back.
+const FADE: Duration = Duration::from_millis(500);
+/// Of the music while sound effects play.
+const DUCKED_VOLUME: f64 = 0.4;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<Music>()
+            .init_resource::<MusicDirector>()
+            .add_systems(
+                Update,
+                direct_music.run_if(not(in_state(GameState::Loading))),
+            );
+    }
+}
+
+/// The channel of the music. Sound effects play in the main one.
+#[derive(Resource)]
+pub struct Music;
+
+#[derive(Resource, Default)]
+pub struct MusicDirector {
+    instance: Option<Handle<AudioInstance>>,
+    /// Of the track being heard, which depends on the settings and on the ducking.
+    level: f64,
+}
+
+fn direct_music(
+    mut director: ResMut<MusicDirector>,
+    audio_assets: Res<AudioAssets>,
+    music: Res<AudioChannel<Music>>,
+    effects: Res<Audio>,
+    settings: Res<Settings>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+) {
+    let level = if effects.is_playing_sound() {
+        settings.music_volume() * DUCKED_VOLUME
+    } else {
+        settings.music_volume()
+    };
+    let Some(instance) = director.instance.as_ref() else {
+        let instance = music
+            .play(audio_assets.background_music.clone())
+            .looped()
+            .with_volume(level)
+            .handle();
+        director.instance = Some(instance);
+        director.level = level;
+        return;
+    };
+    if director.level != level {
+        if let Some(instance) = instances.get_mut(instance) {
+            instance.set_volume(level, AudioTween::linear(FADE));
+        }
+        director.level = level;
+    }
+}
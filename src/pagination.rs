@@ -0,0 +1,336 @@
+//! Fits the texts of the book in their pages.
+//! Once the page has been laid out, the texts of a page that overflows are shrunk down to
+//! [`MIN_FONT_SIZE`]. If that isn't enough, what doesn't fit of the [`Paginated`] text goes on to
+//! the next spread, turned with the arrow, and the rest of the spread waits until it's been read.
+
+#![allow(clippy::too_many_arguments)]
+
+use bevy::{log, prelude::*, text::TextLayoutInfo, window::PrimaryWindow};
+
+use crate::{
+    book::{Arrow, ShowArrow},
+    book_content::TextStyles,
+    menu::{FirstPage, SecondPage},
+    typewriter::Typewriter,
+};
+
+/// Texts aren't shrunk below this size, they're split instead.
+pub const MIN_FONT_SIZE: f32 = 20.;
+/// How much the font shrinks on every try.
+const FONT_SIZE_STEP: f32 = 2.;
+/// Overflows smaller than this, in pixels, are rounding errors.
+const TOLERANCE: f32 = 0.5;
+
+/// A text that shrinks when it doesn't fit in its page.
+#[derive(Component)]
+pub struct FitsInPage {
+    /// Of the normal text, the rest of the sections shrink along with it.
+    font_size: f32,
+}
+
+/// A text that continues in the next spread when it doesn't fit in its page.
+/// It's hidden until it fits.
+#[derive(Component)]
+pub struct Paginated;
+
+/// What's left to read of the [`Paginated`] text.
+#[derive(Resource, Default)]
+pub struct NextPages {
+    sections: Vec<TextSection>,
+    /// Whether the arrow was put there just to keep reading.
+    owns_arrow: bool,
+}
+
+impl NextPages {
+    pub fn has_more(&self) -> bool {
+        !self.sections.is_empty()
+    }
+
+    pub fn take(&mut self) -> Vec<TextSection> {
+        std::mem::take(&mut self.sections)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// The text of a page, that goes on in the next spread if it's too long.
+pub fn paginated(
+    mut text: TextBundle,
+    text_styles: &TextStyles,
+) -> (TextBundle, FitsInPage, Paginated) {
+    text.visibility = Visibility::Hidden;
+    (text, fits_in_page(text_styles), Paginated)
+}
+
+pub fn fits_in_page(text_styles: &TextStyles) -> FitsInPage {
+    FitsInPage {
+        font_size: text_styles.normal.font_size,
+    }
+}
+
+pub fn fit_texts_in_pages(
+    pages: Query<(Entity, &Node, &Style), Or<(With<FirstPage>, With<SecondPage>)>>,
+    children: Query<&Children>,
+    boxes: Query<(&Node, &GlobalTransform, &Style)>,
+    mut texts: Query<(
+        &mut Text,
+        &mut FitsInPage,
+        &TextLayoutInfo,
+        &mut Visibility,
+        Has<Paginated>,
+    )>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    arrows: Query<(), With<Arrow>>,
+    mut next_pages: ResMut<NextPages>,
+    mut show_arrow: EventWriter<ShowArrow>,
+) {
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.resolution.scale_factor())
+        .unwrap_or(1.)
+        * ui_scale.0;
+    for (page, page_node, page_style) in pages.iter() {
+        let page_texts: Vec<Entity> = children
+            .iter_descendants(page)
+            .filter(|&entity| texts.contains(entity))
+            .collect();
+        // Wait until every text of the page has been laid out.
+        let laid_out = page_texts.iter().all(|&entity| {
+            let (text, _, layout, ..) = texts.get(entity).unwrap();
+            !layout.glyphs.is_empty()
+                || text
+                    .sections
+                    .iter()
+                    .all(|section| section.value.trim().is_empty())
+        });
+        if page_texts.is_empty() || !laid_out {
+            continue;
+        }
+
+        let overflow = overflow(page, page_node, page_style, &children, &boxes);
+        if overflow <= TOLERANCE {
+            for &entity in page_texts.iter() {
+                let (_, _, _, mut visibility, _) = texts.get_mut(entity).unwrap();
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            continue;
+        }
+
+        let mut shrunk = false;
+        for &entity in page_texts.iter() {
+            let (mut text, mut fits_in_page, ..) = texts.get_mut(entity).unwrap();
+            if fits_in_page.font_size > MIN_FONT_SIZE {
+                let font_size = (fits_in_page.font_size - FONT_SIZE_STEP).max(MIN_FONT_SIZE);
+                shrink(&mut text, font_size / fits_in_page.font_size);
+                fits_in_page.font_size = font_size;
+                shrunk = true;
+            }
+        }
+        // Measure again once it's been laid out with the smaller font.
+        if shrunk {
+            continue;
+        }
+
+        let paginated = page_texts
+            .iter()
+            .find(|&&entity| matches!(texts.get(entity), Ok((.., true))));
+        let Some(&entity) = paginated else {
+            continue;
+        };
+        let Ok((text_node, ..)) = boxes.get(entity) else {
+            continue;
+        };
+        let available_height = text_node.size().y - overflow;
+        let (mut text, _, layout, mut visibility, _) = texts.get_mut(entity).unwrap();
+        let overflowing_glyph = layout
+            .glyphs
+            .iter()
+            .find(|glyph| (glyph.position.y + glyph.size.y / 2.) / scale_factor > available_height);
+        let split = overflowing_glyph.and_then(|glyph| {
+            split_sections(&text.sections, glyph.section_index, glyph.byte_index)
+        });
+        if let Some((fitting, mut rest)) = split {
+            log::info!("The text doesn't fit in the page, it goes on in the next one");
+            text.sections = fitting;
+            rest.append(&mut next_pages.sections);
+            next_pages.sections = rest;
+            if arrows.is_empty() {
+                show_arrow.send_default();
+                next_pages.owns_arrow = true;
+            }
+        }
+        // Shown as it is if not even a line fits.
+        visibility.set_if_neq(Visibility::Inherited);
+    }
+}
+
+/// While there's text left to read or being written,
+/// the second page only shows the arrow to go on reading.
+pub fn hide_rest_of_spread(
+    mut commands: Commands,
+    mut next_pages: ResMut<NextPages>,
+    paginated: Query<&Visibility, With<Paginated>>,
+    typewriters: Query<&Typewriter>,
+    second_page: Query<&Children, With<SecondPage>>,
+    mut visibilities: Query<&mut Visibility, (Without<Arrow>, Without<Paginated>)>,
+    arrows: Query<Entity, With<Arrow>>,
+) {
+    let reading = next_pages.has_more()
+        || paginated
+            .iter()
+            .any(|visibility| matches!(visibility, Visibility::Hidden))
+        || typewriters.iter().any(Typewriter::is_writing);
+    let visibility = if reading {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    for children in second_page.iter() {
+        for &child in children.iter() {
+            if let Ok(mut child_visibility) = visibilities.get_mut(child) {
+                child_visibility.set_if_neq(visibility);
+            }
+        }
+    }
+    if !reading && next_pages.owns_arrow {
+        for arrow in arrows.iter() {
+            commands.entity(arrow).despawn_recursive();
+        }
+        next_pages.owns_arrow = false;
+    }
+}
+
+/// How much taller the content of the page is than the page itself, negative if it fits.
+fn overflow(
+    page: Entity,
+    page_node: &Node,
+    page_style: &Style,
+    children: &Query<&Children>,
+    boxes: &Query<(&Node, &GlobalTransform, &Style)>,
+) -> f32 {
+    let padding = |val: Val| match val {
+        Val::Px(pixels) => pixels,
+        _ => 0.,
+    };
+    let height =
+        page_node.size().y - padding(page_style.padding.top) - padding(page_style.padding.bottom);
+    let mut top = f32::MAX;
+    let mut bottom = f32::MIN;
+    for &child in children.get(page).into_iter().flatten() {
+        let Ok((node, transform, style)) = boxes.get(child) else {
+            continue;
+        };
+        // Arrows and such are on top of the page, they don't take any space.
+        if let PositionType::Absolute = style.position_type {
+            continue;
+        }
+        let rect = node.logical_rect(transform);
+        top = top.min(rect.min.y - padding(style.margin.top));
+        bottom = bottom.max(rect.max.y + padding(style.margin.bottom));
+    }
+    if top > bottom {
+        return 0.;
+    }
+    bottom - top - height
+}
+
+fn shrink(text: &mut Text, factor: f32) {
+    for section in text.sections.iter_mut() {
+        let font_size = section.style.font_size;
+        // Texts that were already small don't get any smaller.
+        section.style.font_size = (font_size * factor).max(MIN_FONT_SIZE.min(font_size));
+    }
+}
+
+/// Splits the sections before the word of the character at `byte_index` of the section
+/// `section_index`, so no word is cut in half unless it doesn't fit in a page by itself.
+/// Returns `None` if nothing would be left before.
+pub fn split_sections(
+    sections: &[TextSection],
+    section_index: usize,
+    byte_index: usize,
+) -> Option<(Vec<TextSection>, Vec<TextSection>)> {
+    if section_index >= sections.len() {
+        return None;
+    }
+    let mut cut = (section_index, byte_index);
+    let mut end = byte_index;
+    for index in (0..=section_index).rev() {
+        let value = &sections[index].value[..end];
+        if let Some((position, c)) = value.char_indices().rev().find(|(_, c)| c.is_whitespace()) {
+            cut = (index, position + c.len_utf8());
+            break;
+        }
+        if index > 0 {
+            end = sections[index - 1].value.len();
+        }
+    }
+
+    let (section_index, byte_index) = cut;
+    let (before, after) = sections[section_index].value.split_at(byte_index);
+    let style = sections[section_index].style.clone();
+    let mut fitting = sections[..section_index].to_vec();
+    fitting.push(TextSection::new(before, style.clone()));
+    let mut rest = vec![TextSection::new(after, style)];
+    rest.extend_from_slice(&sections[section_index + 1..]);
+
+    // No whitespace left at the end of the page nor at the start of the next one.
+    while let Some(last) = fitting.last_mut() {
+        last.value = last.value.trim_end().to_string();
+        if !last.value.is_empty() {
+            break;
+        }
+        fitting.pop();
+    }
+    while let Some(first) = rest.first_mut() {
+        first.value = first.value.trim_start().to_string();
+        if !first.value.is_empty() {
+            break;
+        }
+        rest.remove(0);
+    }
+    if fitting.is_empty() {
+        return None;
+    }
+    Some((fitting, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(sections: &[TextSection]) -> Vec<&str> {
+        sections
+            .iter()
+            .map(|section| section.value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sections_are_split_between_words() {
+        let sections: Vec<TextSection> = ["E", "l dragón ", "rugió", " muy fuerte"]
+            .into_iter()
+            .map(|value| TextSection::new(value, TextStyle::default()))
+            .collect();
+
+        // In the middle of "muy".
+        let (fitting, rest) = split_sections(&sections, 3, 2).unwrap();
+        assert_eq!(values(&fitting), vec!["E", "l dragón ", "rugió"]);
+        assert_eq!(values(&rest), vec!["muy fuerte"]);
+
+        // In the middle of "rugió", with the space in the section before.
+        let (fitting, rest) = split_sections(&sections, 2, 3).unwrap();
+        assert_eq!(values(&fitting), vec!["E", "l dragón"]);
+        assert_eq!(values(&rest), vec!["rugió", " muy fuerte"]);
+
+        // A word that doesn't fit by itself is cut.
+        let (fitting, rest) = split_sections(&sections, 1, 1).unwrap();
+        assert_eq!(values(&fitting), vec!["E", "l"]);
+        assert_eq!(values(&rest), vec!["dragón ", "rugió", " muy fuerte"]);
+
+        assert!(split_sections(&sections, 0, 0).is_none());
+    }
+}
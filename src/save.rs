@@ -0,0 +1,206 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use bevy::{log, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::book_content::{BookContext, BookGraph};
+
+/// Bump it whenever the book changes in a way that breaks old saves,
+/// like renumbering nodes or changing what a flag means.
+pub const SAVE_VERSION: u32 = 2;
+
+const SAVE_FILE_NAME: &str = "sant_jordi_save.ron";
+
+/// The reader's progress through the book.
+/// When inserted as a resource, the book starts from it instead of from the beginning.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveData {
+    pub version: u32,
+    pub current_node: usize,
+    pub context: BookContext,
+    /// The visited path, with the context each node was left with.
+    pub history: Vec<(usize, BookContext)>,
+}
+
+/// Only the version, to check it before trying to read the rest.
+#[derive(Deserialize)]
+struct SaveVersion {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    WrongVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "couldn't access the save file: {error}"),
+            SaveError::Serialize(error) => write!(f, "couldn't write the save: {error}"),
+            SaveError::Deserialize(error) => write!(f, "couldn't read the save: {error}"),
+            SaveError::WrongVersion(version) => write!(
+                f,
+                "the save has version {version} but version {SAVE_VERSION} was expected"
+            ),
+        }
+    }
+}
+
+impl SaveData {
+    pub fn from_graph(graph: &BookGraph) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            current_node: graph.get_current_index(),
+            context: graph.context.clone(),
+            history: graph.get_history().to_vec(),
+        }
+    }
+
+    /// Returns whether or not the graph could be restored, it can't if the book changed too much.
+    /// Variables added to the book since saving start with their default values.
+    pub fn apply(mut self, graph: &mut BookGraph) -> bool {
+        let defaults = graph.get_initial_context();
+        self.context.declare_missing(defaults);
+        for (_, context) in self.history.iter_mut() {
+            context.declare_missing(defaults);
+        }
+        graph.restore(self.current_node, self.context, self.history)
+    }
+
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(SaveError::Serialize)
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, SaveError> {
+        let SaveVersion { version } = ron::from_str(text).map_err(SaveError::Deserialize)?;
+        if version != SAVE_VERSION {
+            return Err(SaveError::WrongVersion(version));
+        }
+        ron::from_str(text).map_err(SaveError::Deserialize)
+    }
+}
+
+/// Where the files of the game go, like the save or the settings.
+/// `None` when there's no file system, like on the web, or no folder for the data of the user,
+/// so nothing ends up wherever the game was started from.
+pub fn data_path(file_name: &str) -> Option<PathBuf> {
+    if cfg!(target_arch = "wasm32") {
+        return None;
+    }
+    let Some(directory) = std::env::var_os("APPDATA")
+        .or_else(|| std::env::var_os("XDG_DATA_HOME"))
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    else {
+        log::warn!("There's no folder for the data of the user, `{file_name}` isn't kept");
+        return None;
+    };
+    Some(directory.join("sant_jordi").join(file_name))
+}
+
+fn save_path() -> Option<PathBuf> {
+    data_path(SAVE_FILE_NAME)
+}
+
+/// Saves the progress of the reader, logging if it fails.
+pub fn save(graph: &BookGraph) {
+    let Some(path) = save_path() else {
+        return;
+    };
+    let result = SaveData::from_graph(graph).to_ron().and_then(|text| {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(SaveError::Io)?;
+        }
+        fs::write(&path, text).map_err(SaveError::Io)
+    });
+    if let Err(error) = result {
+        log::warn!("Couldn't save the game: {error}");
+    }
+}
+
+/// The saved progress, read once each time the menu is opened.
+#[derive(Resource, Default)]
+pub struct SavedGame(pub Option<SaveData>);
+
+pub fn load_saved_game(mut saved_game: ResMut<SavedGame>) {
+    saved_game.0 = load();
+}
+
+/// Returns the saved progress, if there's any that can still be used.
+pub fn load() -> Option<SaveData> {
+    let path = save_path()?;
+    let text = fs::read_to_string(path).ok()?;
+    match SaveData::from_ron(&text) {
+        Ok(save) => Some(save),
+        Err(error) => {
+            log::warn!("Ignoring the saved game: {error}");
+            None
+        }
+    }
+}
+
+/// Forgets the saved progress, once the book is finished.
+pub fn delete() {
+    if let Some(path) = save_path() {
+        if path.exists() {
+            if let Err(error) = fs::remove_file(path) {
+                log::warn!("Couldn't delete the saved game: {error}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::story::test_book;
+
+    #[test]
+    fn save_round_trip_works() {
+        let mut graph = test_book();
+        graph.choose(1);
+        graph.advance();
+        let save = SaveData::from_graph(&graph);
+        assert_eq!(save.current_node, 26);
+        assert_eq!(save.history.len(), 2);
+
+        let loaded = SaveData::from_ron(&save.to_ron().unwrap()).unwrap();
+        assert_eq!(loaded, save);
+
+        graph.reset();
+        assert!(loaded.apply(&mut graph));
+        assert_eq!(graph.get_current_index(), 26);
+        assert_eq!(SaveData::from_graph(&graph), save);
+    }
+
+    #[test]
+    fn save_checks_version_and_nodes() {
+        let text = SaveData {
+            version: SAVE_VERSION + 1,
+            current_node: 0,
+            context: BookContext::default(),
+            history: Vec::new(),
+        }
+        .to_ron()
+        .unwrap();
+        assert!(matches!(
+            SaveData::from_ron(&text),
+            Err(SaveError::WrongVersion(version)) if version == SAVE_VERSION + 1
+        ));
+
+        let mut graph = test_book();
+        let save = SaveData {
+            version: SAVE_VERSION,
+            current_node: 1000,
+            context: BookContext::default(),
+            history: vec![(0, BookContext::default())],
+        };
+        assert!(!save.apply(&mut graph));
+        assert_eq!(graph.get_current_index(), 0);
+    }
+}
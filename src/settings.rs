@@ -0,0 +1,545 @@
+//! The options of the reader, kept between plays and changed in a page of the menu book.
+//! They're applied as soon as they change: the volume of the audio channels, the size of the
+//! texts of the book, how fast they're written and the mode of the window.
+
+use std::{fmt, fs, io};
+
+use bevy::{log, prelude::*, window::PrimaryWindow, window::WindowMode};
+use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{read_actions, Bindings},
+    book::{BUTTON_HOVER_COLOR, BUTTON_NORMAL_COLOR},
+    controls,
+    loading::FontAssets,
+    localization::Localizer,
+    menu::{MenuPage, MENU_BUTTON_RED},
+    save,
+    typewriter::TextSpeed,
+    GameState,
+};
+
+const SETTINGS_FILE_NAME: &str = "sant_jordi_settings.ron";
+
+const VOLUME_STEP: f64 = 0.1;
+const FONT_SCALE_STEP: f32 = 0.1;
+const MIN_FONT_SCALE: f32 = 0.8;
+const MAX_FONT_SCALE: f32 = 1.5;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .add_systems(
+                Update,
+                (
+                    apply_settings.run_if(resource_changed::<Settings>),
+                    adjust_settings,
+                    click_page_buttons,
+                    controls::click_rebind_buttons,
+                    controls::click_reset_button,
+                ),
+            )
+            .add_systems(
+                PreUpdate,
+                controls::bind_pressed_input
+                    .after(read_actions)
+                    .run_if(in_state(GameState::Menu)),
+            );
+    }
+}
+
+/// Missing options, like the ones added after the file was written, get their default values.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// From 0 to 1, like the other volume.
+    pub music_volume: f64,
+    pub effects_volume: f64,
+    /// Silences everything without losing the volumes.
+    pub muted: bool,
+    pub text_pace: TextPace,
+    /// Of the texts of the book, not of the menu.
+    pub font_scale: f32,
+    pub fullscreen: bool,
+    /// What triggers each of the [`crate::actions::Action`]s.
+    pub bindings: Bindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.,
+            effects_volume: 1.,
+            muted: false,
+            text_pace: TextPace::Normal,
+            font_scale: 1.,
+            fullscreen: true,
+            bindings: Bindings::default(),
+        }
+    }
+}
+
+/// How fast the texts are written, see [`TextSpeed`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextPace {
+    Slow,
+    Normal,
+    Fast,
+    /// The texts are shown all at once.
+    Instant,
+}
+
+impl TextPace {
+    const ALL: [TextPace; 4] = [
+        TextPace::Slow,
+        TextPace::Normal,
+        TextPace::Fast,
+        TextPace::Instant,
+    ];
+
+    pub fn characters_per_second(self) -> f32 {
+        match self {
+            TextPace::Slow => 20.,
+            TextPace::Normal => 40.,
+            TextPace::Fast => 80.,
+            TextPace::Instant => 0.,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            TextPace::Slow => "settings-pace-slow",
+            TextPace::Normal => "settings-pace-normal",
+            TextPace::Fast => "settings-pace-fast",
+            TextPace::Instant => "settings-pace-instant",
+        }
+    }
+}
+
+/// Each of the rows of the settings page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingOption {
+    MusicVolume,
+    EffectsVolume,
+    Mute,
+    TextPace,
+    FontScale,
+    Fullscreen,
+}
+
+impl SettingOption {
+    const ALL: [SettingOption; 6] = [
+        SettingOption::MusicVolume,
+        SettingOption::EffectsVolume,
+        SettingOption::Mute,
+        SettingOption::TextPace,
+        SettingOption::FontScale,
+        SettingOption::Fullscreen,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            SettingOption::MusicVolume => "settings-music-volume",
+            SettingOption::EffectsVolume => "settings-effects-volume",
+            SettingOption::Mute => "settings-mute",
+            SettingOption::TextPace => "settings-text-pace",
+            SettingOption::FontScale => "settings-font-scale",
+            SettingOption::Fullscreen => "settings-window",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(error) => write!(f, "couldn't access the settings file: {error}"),
+            SettingsError::Serialize(error) => write!(f, "couldn't write the settings: {error}"),
+            SettingsError::Deserialize(error) => write!(f, "couldn't read the settings: {error}"),
+        }
+    }
+}
+
+impl Settings {
+    /// What the music actually sounds at.
+    pub fn music_volume(&self) -> f64 {
+        if self.muted {
+            0.
+        } else {
+            self.music_volume
+        }
+    }
+
+    /// The volume of the channel the sound effects play on, their own volumes are relative to it.
+    pub fn effects_volume(&self) -> f64 {
+        if self.muted {
+            0.
+        } else {
+            self.effects_volume
+        }
+    }
+
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        }
+    }
+
+    /// Moves the option to its next value, or to the previous one, without going past the ends.
+    /// Options that are either on or off are toggled.
+    pub fn adjust(&mut self, option: SettingOption, forward: bool) {
+        match option {
+            SettingOption::MusicVolume => {
+                self.music_volume = step_volume(self.music_volume, forward);
+            }
+            SettingOption::EffectsVolume => {
+                self.effects_volume = step_volume(self.effects_volume, forward);
+            }
+            SettingOption::Mute => self.muted = !self.muted,
+            SettingOption::TextPace => {
+                let index = TextPace::ALL
+                    .iter()
+                    .position(|&pace| pace == self.text_pace)
+                    .unwrap_or_default();
+                let index = if forward {
+                    (index + 1).min(TextPace::ALL.len() - 1)
+                } else {
+                    index.saturating_sub(1)
+                };
+                self.text_pace = TextPace::ALL[index];
+            }
+            SettingOption::FontScale => {
+                let step = if forward {
+                    FONT_SCALE_STEP
+                } else {
+                    -FONT_SCALE_STEP
+                };
+                // Rounded, so the steps don't drift.
+                self.font_scale = ((self.font_scale + step) * 10.)
+                    .round()
+                    .clamp(MIN_FONT_SCALE * 10., MAX_FONT_SCALE * 10.)
+                    / 10.;
+            }
+            SettingOption::Fullscreen => self.fullscreen = !self.fullscreen,
+        }
+    }
+
+    fn value_text(&self, option: SettingOption, localizer: &Localizer) -> String {
+        let on_off = |on: bool| localizer.ui(if on { "settings-on" } else { "settings-off" });
+        match option {
+            SettingOption::MusicVolume => percentage(self.music_volume),
+            SettingOption::EffectsVolume => percentage(self.effects_volume),
+            SettingOption::Mute => on_off(self.muted),
+            SettingOption::TextPace => localizer.ui(self.text_pace.key()),
+            SettingOption::FontScale => percentage(self.font_scale.into()),
+            SettingOption::Fullscreen => localizer.ui(if self.fullscreen {
+                "settings-fullscreen"
+            } else {
+                "settings-windowed"
+            }),
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, SettingsError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(SettingsError::Serialize)
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, SettingsError> {
+        ron::from_str(text).map_err(SettingsError::Deserialize)
+    }
+
+    /// The saved settings, or the default ones if there aren't any that can be read.
+    pub fn load() -> Self {
+        let Some(text) =
+            save::data_path(SETTINGS_FILE_NAME).and_then(|path| fs::read_to_string(path).ok())
+        else {
+            return Self::default();
+        };
+        Self::from_ron(&text).unwrap_or_else(|error| {
+            log::warn!("Ignoring the settings: {error}");
+            Self::default()
+        })
+    }
+
+    /// Logs if it fails, the settings still apply until the game is closed.
+    pub fn save(&self) {
+        let Some(path) = save::data_path(SETTINGS_FILE_NAME) else {
+            return;
+        };
+        let result = self.to_ron().and_then(|text| {
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory).map_err(SettingsError::Io)?;
+            }
+            fs::write(&path, text).map_err(SettingsError::Io)
+        });
+        if let Err(error) = result {
+            log::warn!("Couldn't save the settings: {error}");
+        }
+    }
+}
+
+fn step_volume(volume: f64, forward: bool) -> f64 {
+    let step = if forward { VOLUME_STEP } else { -VOLUME_STEP };
+    ((volume + step) * 10.).round().clamp(0., 10.) / 10.
+}
+
+fn percentage(value: f64) -> String {
+    format!("{}%", (value * 100.).round())
+}
+
+/// The music is applied by [`crate::music`], which sets the volume of each of its tracks.
+fn apply_settings(
+    settings: Res<Settings>,
+    audio: Res<Audio>,
+    mut text_speed: ResMut<TextSpeed>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    audio.set_volume(settings.effects_volume());
+    text_speed.characters_per_second = settings.text_pace.characters_per_second();
+    for mut window in windows.iter_mut() {
+        let mode = settings.window_mode();
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+    // They were just loaded otherwise.
+    if !settings.is_added() {
+        settings.save();
+    }
+}
+
+/// The settings page is shown instead of the menu on the second page.
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+struct AdjustButton {
+    option: SettingOption,
+    forward: bool,
+}
+
+/// Shows another page of the menu book.
+#[derive(Component)]
+pub struct PageButton(pub MenuPage);
+
+pub fn text_button(
+    parent: &mut ChildBuilder,
+    text: String,
+    font_size: f32,
+    fonts: &FontAssets,
+    button: impl Bundle,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                background_color: Color::NONE.into(),
+                style: Style {
+                    padding: UiRect::horizontal(Val::Px(10.)),
+                    ..default()
+                },
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: fonts.normal.clone(),
+                    font_size,
+                    color: MENU_BUTTON_RED,
+                },
+            ));
+        });
+}
+
+/// Each option has its name, its value and the buttons to change it.
+pub fn spawn_settings_page(
+    parent: &mut ChildBuilder,
+    settings: &Settings,
+    fonts: &FontAssets,
+    localizer: &Localizer,
+) {
+    let text_style = |font_size| TextStyle {
+        font: fonts.normal.clone(),
+        font_size,
+        color: Color::BLACK,
+    };
+    parent.spawn(TextBundle::from_section(
+        localizer.ui("menu-settings"),
+        TextStyle {
+            color: MENU_BUTTON_RED,
+            ..text_style(40.)
+        },
+    ));
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for option in SettingOption::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            margin: UiRect::vertical(Val::Px(5.)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            localizer.ui(option.key()),
+                            text_style(25.),
+                        ));
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    display: Display::Flex,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                let adjust = |forward| AdjustButton { option, forward };
+                                text_button(parent, "<".to_owned(), 25., fonts, adjust(false));
+                                parent.spawn(
+                                    TextBundle::from_section(
+                                        settings.value_text(option, localizer),
+                                        text_style(25.),
+                                    )
+                                    .with_style(Style {
+                                        min_width: Val::Px(120.),
+                                        justify_content: JustifyContent::Center,
+                                        ..default()
+                                    })
+                                    .with_text_justify(JustifyText::Center),
+                                );
+                                text_button(parent, ">".to_owned(), 25., fonts, adjust(true));
+                            });
+                    });
+            }
+        });
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            let controls = MenuPage::Controls { rebinding: None };
+            text_button(
+                parent,
+                localizer.ui("settings-controls"),
+                30.,
+                fonts,
+                PageButton(controls),
+            );
+            text_button(
+                parent,
+                localizer.ui("settings-back"),
+                30.,
+                fonts,
+                PageButton(MenuPage::Main),
+            );
+        });
+}
+
+fn adjust_settings(
+    mut interaction_query: Query<
+        (&Interaction, &AdjustButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, adjust, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                settings.adjust(adjust.option, adjust.forward);
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+fn click_page_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &PageButton, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+    mut menu_page: ResMut<MenuPage>,
+) {
+    for (interaction, page, mut color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *menu_page = page.0;
+            }
+            Interaction::Hovered => {
+                *color = BUTTON_HOVER_COLOR.into();
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL_COLOR.into();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_stay_in_range_and_round_trip() {
+        let mut settings = Settings::default();
+        for _ in 0..15 {
+            settings.adjust(SettingOption::MusicVolume, true);
+            settings.adjust(SettingOption::FontScale, true);
+            settings.adjust(SettingOption::TextPace, true);
+        }
+        assert_eq!(settings.music_volume, 1.);
+        assert_eq!(settings.font_scale, MAX_FONT_SCALE);
+        assert_eq!(settings.text_pace, TextPace::Instant);
+        for _ in 0..3 {
+            settings.adjust(SettingOption::EffectsVolume, false);
+        }
+        assert_eq!(settings.effects_volume(), 0.7);
+        settings.adjust(SettingOption::Mute, true);
+        assert_eq!(settings.effects_volume(), 0.);
+        assert_eq!(settings.window_mode(), WindowMode::BorderlessFullscreen);
+
+        let loaded = Settings::from_ron(&settings.to_ron().unwrap()).unwrap();
+        assert_eq!(loaded, settings);
+        // Options missing from the file keep their default values.
+        let loaded = Settings::from_ron("(muted: true)").unwrap();
+        assert!(loaded.muted);
+        assert_eq!(loaded.text_pace, TextPace::Normal);
+    }
+}
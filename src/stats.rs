@@ -0,0 +1,304 @@
+//! Numbers about the length of the story, to plan how long playing it takes.
+
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use crate::{
+    book_content::{BookContext, BookGraph, Text},
+    graph::{Node, PlaythroughEnd, Step},
+    localization::{self, Locales, LocalizationError},
+    markup,
+    menu::Language,
+    story::StoryFile,
+};
+
+/// Of people reading a book aloud, or slowly, at a fair.
+pub const WORDS_PER_MINUTE: f32 = 150.;
+/// About what people spend at a booth.
+pub const BOOTH_MINUTES: f32 = 5.;
+
+/// A way of getting to an ending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    pub nodes: Vec<usize>,
+    /// Read in that playthrough: the pages, the choices offered and what follows each choice.
+    pub words: usize,
+}
+
+impl Path {
+    pub fn reading_minutes(&self) -> f32 {
+        self.words as f32 / WORDS_PER_MINUTE
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub nodes: usize,
+    /// Number of choices of each fork.
+    pub choices_per_fork: BTreeMap<usize, usize>,
+    /// Simple nodes without `next`.
+    pub endings: usize,
+    pub reachable_endings: usize,
+    /// Read on each node the first time it's shown, choices included.
+    pub words_per_node: BTreeMap<usize, usize>,
+    /// Every playthrough that gets to an ending, see [`crate::graph::Graph::explore`].
+    pub paths: Vec<Path>,
+    /// Playthroughs that don't get to an ending, `story lint` tells why.
+    pub broken_paths: usize,
+}
+
+impl Stats {
+    pub fn new(story: &StoryFile) -> Result<Self, LocalizationError> {
+        let locales = Locales::new(Arc::new(localization::story_ftl(story)?), []);
+        let graph = story.to_graph();
+        let exploration = graph.explore();
+
+        let mut choices_per_fork = BTreeMap::new();
+        let mut endings = 0;
+        let mut words_per_node = BTreeMap::new();
+        for index in graph.get_indices() {
+            let node = graph
+                .get_node(index)
+                .expect("The index comes from the graph");
+            let context = exploration
+                .context_at(index)
+                .unwrap_or(graph.get_initial_context());
+            match node {
+                Node::Simple { next: None, .. } => endings += 1,
+                Node::Fork { choices, .. } => {
+                    choices_per_fork.insert(index, choices.len());
+                }
+                _ => {}
+            }
+            let step = Step {
+                node: index,
+                choice: None,
+                context: context.clone(),
+            };
+            words_per_node.insert(index, step_words(&graph, &locales, &step));
+        }
+
+        let mut paths = Vec::new();
+        let mut broken_paths = 0;
+        for playthrough in exploration.playthroughs.iter() {
+            if !matches!(playthrough.end, PlaythroughEnd::Ending(_)) {
+                broken_paths += 1;
+                continue;
+            }
+            paths.push(Path {
+                nodes: playthrough.nodes(),
+                words: playthrough
+                    .steps
+                    .iter()
+                    .map(|step| step_words(&graph, &locales, step))
+                    .sum(),
+            });
+        }
+
+        Ok(Self {
+            nodes: graph.get_indices().len(),
+            choices_per_fork,
+            endings,
+            reachable_endings: exploration.endings().len(),
+            words_per_node,
+            paths,
+            broken_paths,
+        })
+    }
+
+    pub fn shortest(&self) -> Option<&Path> {
+        self.paths.iter().min_by_key(|path| path.words)
+    }
+
+    pub fn longest(&self) -> Option<&Path> {
+        self.paths.iter().max_by_key(|path| path.words)
+    }
+
+    pub fn average_words(&self) -> f32 {
+        if self.paths.is_empty() {
+            return 0.;
+        }
+        let words: usize = self.paths.iter().map(|path| path.words).sum();
+        words as f32 / self.paths.len() as f32
+    }
+
+    pub fn average_nodes(&self) -> f32 {
+        if self.paths.is_empty() {
+            return 0.;
+        }
+        let nodes: usize = self.paths.iter().map(|path| path.nodes.len()).sum();
+        nodes as f32 / self.paths.len() as f32
+    }
+
+    pub fn average_choices(&self) -> f32 {
+        if self.choices_per_fork.is_empty() {
+            return 0.;
+        }
+        let choices: usize = self.choices_per_fork.values().sum();
+        choices as f32 / self.choices_per_fork.len() as f32
+    }
+}
+
+/// Words read on a step of a playthrough. Forks count every choice shown, along with the
+/// text that follows the one taken, if any.
+fn step_words(graph: &BookGraph, locales: &Locales, step: &Step<BookContext>) -> usize {
+    let index = step.node;
+    let context = &step.context;
+    let words = |key: String, text: &Text| {
+        let text = locales.text(Language::DEFAULT, &key, text, context);
+        markup::plain_text(&text).split_whitespace().count()
+    };
+    match graph.get_node(index) {
+        None => 0,
+        Some(Node::Simple { content, extra, .. }) => {
+            words(localization::node_key(index, "text"), &content.text)
+                + words(
+                    localization::node_key(index, "additional-text"),
+                    &extra.additional_text,
+                )
+        }
+        Some(Node::Fork { content, choices }) => {
+            let shown: usize = choices
+                .iter()
+                .enumerate()
+                .filter(|(_, choice)| choice.is_shown(context))
+                .map(|(position, choice)| {
+                    words(
+                        localization::choice_key(index, position, "text"),
+                        &choice.text,
+                    )
+                })
+                .sum();
+            let taken = step.choice.map_or(0, |position| {
+                words(
+                    localization::choice_key(index, position, "additional-text"),
+                    &choices[position].additional_text,
+                )
+            });
+            words(localization::node_key(index, "text"), &content.text) + shown + taken
+        }
+    }
+}
+
+fn write_path(f: &mut fmt::Formatter<'_>, name: &str, path: &Path) -> fmt::Result {
+    let nodes: Vec<String> = path.nodes.iter().map(ToString::to_string).collect();
+    writeln!(
+        f,
+        "  {name}: {} pages, {} words, {:.1} minutes ({})",
+        path.nodes.len(),
+        path.words,
+        path.reading_minutes(),
+        nodes.join(" → ")
+    )
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Nodes: {} ({} simple, {} forks)",
+            self.nodes,
+            self.nodes - self.choices_per_fork.len(),
+            self.choices_per_fork.len()
+        )?;
+        writeln!(
+            f,
+            "Endings: {} ({} can be reached)",
+            self.endings, self.reachable_endings
+        )?;
+
+        writeln!(
+            f,
+            "\nChoices per fork: {:.1} on average",
+            self.average_choices()
+        )?;
+        for (node, choices) in self.choices_per_fork.iter() {
+            writeln!(f, "  node {node}: {choices}")?;
+        }
+
+        let words: usize = self.words_per_node.values().sum();
+        writeln!(f, "\nWords: {words} in total")?;
+        for (node, words) in self.words_per_node.iter() {
+            writeln!(f, "  node {node}: {words}")?;
+        }
+
+        writeln!(
+            f,
+            "\nPlaythroughs to an ending: {}, at {WORDS_PER_MINUTE} words per minute",
+            self.paths.len()
+        )?;
+        if self.broken_paths > 0 {
+            writeln!(f, "  {} more never get to one", self.broken_paths)?;
+        }
+        if let (Some(shortest), Some(longest)) = (self.shortest(), self.longest()) {
+            write_path(f, "Shortest", shortest)?;
+            write_path(f, "Longest", longest)?;
+            let average_words = self.average_words();
+            writeln!(
+                f,
+                "  Average: {:.1} pages, {average_words:.0} words, {:.1} minutes",
+                self.average_nodes(),
+                average_words / WORDS_PER_MINUTE
+            )?;
+            let too_long = self
+                .paths
+                .iter()
+                .filter(|path| path.reading_minutes() > BOOTH_MINUTES)
+                .count();
+            writeln!(
+                f,
+                "  Longer than {BOOTH_MINUTES} minutes: {too_long} of {}",
+                self.paths.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_counted_as_they_are_read() {
+        let story = StoryFile::from_ron(
+            r#"(
+                variables: {"nombre": Text("Jordi")},
+                nodes: [
+                    (0, Fork(
+                        content: (text: "Había una vez"),
+                        choices: [
+                            (text: "un *dragón*", additional_text: "que volaba", next: 1),
+                            (text: "{ $nombre }", next: 2),
+                        ],
+                    )),
+                    (1, Simple(content: (text: "Y voló"), next: Some(2))),
+                    (2, Simple(content: (text: "Fin"), next: None)),
+                ],
+            )"#,
+        )
+        .unwrap();
+        let stats = Stats::new(&story).unwrap();
+        assert_eq!(stats.nodes, 3);
+        assert_eq!(stats.endings, 1);
+        assert_eq!(stats.choices_per_fork, BTreeMap::from([(0, 2)]));
+        assert_eq!(
+            stats.words_per_node,
+            BTreeMap::from([(0, 3 + 2 + 1), (1, 2), (2, 1)])
+        );
+        assert_eq!(
+            stats.paths,
+            vec![
+                Path {
+                    nodes: vec![0, 1, 2],
+                    words: 6 + 2 + 2 + 1,
+                },
+                Path {
+                    nodes: vec![0, 2],
+                    words: 6 + 1,
+                },
+            ]
+        );
+        assert_eq!(stats.shortest().unwrap().nodes, vec![0, 2]);
+        assert_eq!(stats.average_words(), 9.);
+    }
+}
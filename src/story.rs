@@ -0,0 +1,432 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt, fs, io,
+    path::Path,
+};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
+
+use crate::{
+    book_content::{BookContext, BookGraph, Next, NodeChoice, SimpleContent, SimpleExtra},
+    expression::{EvalError, Expression, Type, Variables},
+    graph::Node,
+    localization::{self, LocalizationError},
+    markup::{self, MarkupError, Palette},
+    variables::{Variable, VariableStore},
+};
+
+/// Where the book is, from the root of the repository, for the tools.
+pub const BOOK_PATH: &str = "assets/story/book.story.ron";
+/// Where the translations are, from the root of the repository, for the tools.
+pub const LOCALES_PATH: &str = "assets/locales";
+/// Where the images are, from the root of the repository, for the tools.
+pub const ASSETS_PATH: &str = "assets";
+
+pub type BookNode = Node<SimpleContent, SimpleExtra, NodeChoice>;
+
+pub fn illustration_path(name: &str) -> String {
+    format!("illustrations/{name}.png")
+}
+
+pub fn decoration_path(name: &str) -> String {
+    format!("textures/{name}.png")
+}
+
+pub fn sound_path(name: &str) -> String {
+    format!("audio/{name}.ogg")
+}
+
+/// A book as it's written in a `.story.ron` file.
+#[derive(Deserialize)]
+pub struct StoryFile {
+    /// Every variable the story uses, along with the value it starts with.
+    #[serde(default)]
+    pub variables: BTreeMap<String, Variable>,
+    /// Colours to use by name in the markup of the texts and the colours of the pages, as hex.
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+    pub nodes: Vec<(usize, BookNode)>,
+}
+
+#[derive(Debug)]
+pub enum StoryError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    DuplicateNode(usize),
+    /// An expression that would go wrong when evaluated.
+    Logic {
+        node: usize,
+        error: EvalError,
+    },
+    InvalidColor {
+        node: usize,
+        color: String,
+    },
+    InvalidNamedColor {
+        name: String,
+        color: String,
+    },
+    Markup {
+        node: usize,
+        error: MarkupError,
+    },
+    /// The texts can't be turned into Fluent messages, so they couldn't be translated.
+    Messages(LocalizationError),
+}
+
+impl fmt::Display for StoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoryError::Io(error) => write!(f, "couldn't read the story: {error}"),
+            StoryError::Parse(error) => write!(f, "couldn't parse the story: {error}"),
+            StoryError::DuplicateNode(node) => write!(f, "node {node} is written more than once"),
+            StoryError::Logic { node, error } => write!(f, "node {node}: {error}"),
+            StoryError::InvalidColor { node, color } => {
+                write!(f, "node {node} uses `{color}`, which isn't a colour")
+            }
+            StoryError::InvalidNamedColor { name, color } => {
+                write!(f, "`{name}` is `{color}`, which isn't a hex colour")
+            }
+            StoryError::Markup { node, error } => write!(f, "node {node}: {error}"),
+            StoryError::Messages(error) => write!(f, "the texts of the story: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for StoryError {}
+
+impl StoryFile {
+    /// Parses and checks the parts the graph can't, like the types of expressions.
+    /// Checking the graph itself is left to [`BookGraph::validate`].
+    pub fn from_ron(text: &str) -> Result<Self, StoryError> {
+        let story: StoryFile = ron::from_str(text).map_err(StoryError::Parse)?;
+        let context = story.initial_context();
+        let palette = story.palette()?;
+        let mut indices = HashSet::new();
+        for (index, node) in story.nodes.iter() {
+            if !indices.insert(*index) {
+                return Err(StoryError::DuplicateNode(*index));
+            }
+            check_node(*index, node, &context, &palette)?;
+        }
+        Ok(story)
+    }
+
+    pub fn initial_context(&self) -> BookContext {
+        VariableStore::new(self.variables.clone())
+    }
+
+    pub fn palette(&self) -> Result<Palette, StoryError> {
+        Palette::with_colors(&self.colors).map_err(|name| StoryError::InvalidNamedColor {
+            color: self.colors[&name].clone(),
+            name,
+        })
+    }
+
+    pub fn to_graph(&self) -> BookGraph {
+        let mut graph = BookGraph::with_context(self.initial_context());
+        for (index, node) in self.nodes.iter() {
+            graph.add_node(*index, node.clone());
+        }
+        graph
+    }
+
+    /// Names of every image the story shows, as asset paths.
+    pub fn image_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for (_, node) in self.nodes.iter() {
+            let content = match node {
+                Node::Simple { content, extra, .. } => {
+                    paths.extend(extra.illustration.as_deref().map(illustration_path));
+                    paths.extend(extra.decorations.iter().map(|name| decoration_path(name)));
+                    content
+                }
+                Node::Fork { content, choices } => {
+                    for choice in choices.iter() {
+                        paths.extend(choice.illustration.as_deref().map(illustration_path));
+                    }
+                    content
+                }
+            };
+            paths.extend(content.decorations.iter().map(|name| decoration_path(name)));
+        }
+        paths
+    }
+
+    pub fn sound_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for (_, node) in self.nodes.iter() {
+            let content = match node {
+                Node::Simple { content, extra, .. } => {
+                    paths.extend(extra.sounds.iter().map(|cue| sound_path(&cue.sound)));
+                    content
+                }
+                Node::Fork { content, choices } => {
+                    for choice in choices.iter() {
+                        paths.extend(choice.sounds.iter().map(|cue| sound_path(&cue.sound)));
+                    }
+                    content
+                }
+            };
+            paths.extend(content.sounds.iter().map(|cue| sound_path(&cue.sound)));
+        }
+        paths
+    }
+}
+
+/// The checks of [`StoryFile::from_ron`] for a node, stopping at the first mistake.
+pub fn check_node(
+    index: usize,
+    node: &BookNode,
+    context: &BookContext,
+    palette: &Palette,
+) -> Result<(), StoryError> {
+    let types = |name: &str| context.get_variable(name).map(|value| value.get_type());
+    let expect = |expression: &Expression, expected: Type| {
+        let found = expression.check(&types)?;
+        if found == expected {
+            Ok(())
+        } else {
+            Err(EvalError::WrongType {
+                expected,
+                found,
+                expression: expression.to_string(),
+            })
+        }
+    };
+    let mut conditions = Vec::new();
+    let mut texts = Vec::new();
+    let content = match node {
+        Node::Simple { content, extra, .. } => {
+            conditions.extend(extra.additional_text.conditions());
+            texts.extend(extra.additional_text.strings());
+            content
+        }
+        Node::Fork { content, choices } => {
+            for choice in choices.iter() {
+                conditions.extend(choice.conditions());
+                texts.extend(choice.texts().into_iter().flat_map(|text| text.strings()));
+                if let Next::Expression(next) = &choice.next {
+                    expect(next, Type::Int)
+                        .map_err(|error| StoryError::Logic { node: index, error })?;
+                }
+                choice
+                    .state_change
+                    .check(&types)
+                    .map_err(|error| StoryError::Logic { node: index, error })?;
+            }
+            content
+        }
+    };
+    conditions.extend(content.text.conditions());
+    texts.extend(content.text.strings());
+    for condition in conditions {
+        expect(condition, Type::Bool).map_err(|error| StoryError::Logic { node: index, error })?;
+    }
+    for text in texts {
+        markup::parse(text, palette).map_err(|error| StoryError::Markup { node: index, error })?;
+    }
+    let colors = [&content.first_letter_color, &content.highlighted_color];
+    if let Some(color) = colors
+        .into_iter()
+        .flatten()
+        .find(|color| palette.get(color).is_none())
+    {
+        return Err(StoryError::InvalidColor {
+            node: index,
+            color: color.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Reads the book from disk, for the tools.
+pub fn read_book(path: impl AsRef<Path>) -> Result<BookGraph, StoryError> {
+    let text = fs::read_to_string(path).map_err(StoryError::Io)?;
+    Ok(StoryFile::from_ron(&text)?.to_graph())
+}
+
+/// The book as it was when compiled.
+#[cfg(test)]
+pub fn test_book() -> BookGraph {
+    StoryFile::from_ron(include_str!("../assets/story/book.story.ron"))
+        .unwrap()
+        .to_graph()
+}
+
+#[derive(Asset, TypePath)]
+pub struct Story {
+    pub file: StoryFile,
+    /// Keeps the images of the story loaded.
+    #[allow(dead_code)]
+    images: Vec<Handle<Image>>,
+    /// Keeps the sounds of the story loaded.
+    #[allow(dead_code)]
+    sounds: Vec<Handle<AudioSource>>,
+}
+
+#[derive(Default)]
+pub struct StoryLoader;
+
+impl AssetLoader for StoryLoader {
+    type Asset = Story;
+    type Settings = ();
+    type Error = StoryError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Story, StoryError>> {
+        Box::pin(async move {
+            let mut text = String::new();
+            reader
+                .read_to_string(&mut text)
+                .await
+                .map_err(StoryError::Io)?;
+            let file = StoryFile::from_ron(&text)?;
+            localization::story_ftl(&file).map_err(StoryError::Messages)?;
+            let images = file
+                .image_paths()
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect();
+            let sounds = file
+                .sound_paths()
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect();
+            Ok(Story {
+                file,
+                images,
+                sounds,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["story.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::{book_content::SoundCue, graph::GraphError};
+
+    #[test]
+    fn book_is_valid() {
+        assert_eq!(test_book().validate(), vec![]);
+    }
+
+    #[test]
+    fn every_playthrough_reaches_an_ending() {
+        let exploration = test_book().explore();
+        let broken: Vec<_> = exploration
+            .broken()
+            .map(|playthrough| (playthrough.nodes(), &playthrough.end))
+            .collect();
+        assert_eq!(broken, vec![]);
+        assert_eq!(
+            exploration.endings(),
+            BTreeSet::from([7, 16, 23, 24, 30, 33, 37, 40, 42, 43])
+        );
+    }
+
+    #[test]
+    fn story_files_are_checked() {
+        let node = |text: &str| format!("({text}, Simple(content: (text: \"Fin\"), next: None))");
+        let story = format!("(nodes: [{}, {}])", node("0"), node("0"));
+        assert!(matches!(
+            StoryFile::from_ron(&story),
+            Err(StoryError::DuplicateNode(0))
+        ));
+
+        let story = r#"(nodes: [(0, Fork(content: (text: "Hola"), choices: [
+            (text: "Adiós", state_change: "volar = true", next: 1),
+        ]))])"#;
+        assert!(matches!(
+            StoryFile::from_ron(story),
+            Err(StoryError::Logic { node: 0, error: EvalError::UnknownVariable(name) }) if name == "volar"
+        ));
+
+        let story = r#"(variables: {"fan_dragones": Bool(false)}, nodes: [(0, Fork(content: (text: "Hola"), choices: [
+            (text: "Adiós", next: "if fan_dragones { 1 } else { fan_dragones }"),
+        ]))])"#;
+        assert!(matches!(
+            StoryFile::from_ron(story),
+            Err(StoryError::Logic {
+                node: 0,
+                error: EvalError::WrongType { .. }
+            })
+        ));
+
+        let story = r#"(nodes: [(0, Simple(content: (text: "Hola", highlighted_color: Some("rojo")), next: None))])"#;
+        assert!(matches!(
+            StoryFile::from_ron(story),
+            Err(StoryError::InvalidColor { node: 0, .. })
+        ));
+
+        let story = r#"(nodes: [(0, Simple(content: (text: "Un *dragón"), next: None))])"#;
+        assert!(matches!(
+            StoryFile::from_ron(story),
+            Err(StoryError::Markup { node: 0, error }) if error.position == 3
+        ));
+    }
+
+    #[test]
+    fn forks_without_a_choice_to_take_are_dead_ends() {
+        let story = r#"(variables: {"llave": Bool(false)}, nodes: [
+            (0, Fork(content: (text: "Una puerta"), choices: [
+                (text: "Abrir", next: 1, availability: Locked(condition: "llave", reason: "Cerrada")),
+                (text: "Forzar", next: 1, availability: Hidden("llave")),
+            ])),
+            (1, Simple(content: (text: "Fin"), next: None)),
+        ])"#;
+        let mut graph = StoryFile::from_ron(story).unwrap().to_graph();
+        assert_eq!(
+            graph.try_get_playable_node().err(),
+            Some(GraphError::DeadEnd(0))
+        );
+        graph.context.set_bool("llave", true).unwrap();
+        assert!(graph.try_get_playable_node().is_ok());
+    }
+
+    #[test]
+    fn sounds_are_found_wherever_they_are() {
+        let story = StoryFile::from_ron(
+            r#"(nodes: [
+                (0, Fork(
+                    content: (text: "Hola", sounds: [(sound: "flying", volume: 0.5, delay: 1.2)]),
+                    choices: [(text: "Adiós", sounds: [(sound: "punch")], next: 1)],
+                )),
+                (1, Simple(content: (text: "Fin"), extra: (sounds: [(sound: "roar")]), next: None)),
+            ])"#,
+        )
+        .unwrap();
+        assert_eq!(
+            story.sound_paths(),
+            vec!["audio/punch.ogg", "audio/flying.ogg", "audio/roar.ogg"]
+        );
+        let Node::Fork { choices, .. } = &story.nodes[0].1 else {
+            panic!("Node 0 is a fork");
+        };
+        assert_eq!(
+            choices[0].sounds,
+            vec![SoundCue {
+                sound: "punch".to_string(),
+                volume: 1.,
+                delay: 0.,
+            }]
+        );
+    }
+}
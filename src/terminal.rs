@@ -0,0 +1,311 @@
+//! Plays the book in a terminal, without the game, to try branches out in seconds.
+//! The texts are shown without their markup and the choices are picked by their number.
+
+use std::{
+    fmt, fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use fluent_bundle::FluentResource;
+
+use crate::{
+    book_content::{BookGraph, Text},
+    graph::{GraphError, Node},
+    localization::{self, Locales, LocalizationError},
+    markup,
+    menu::Language,
+    story::{StoryError, StoryFile},
+};
+
+#[derive(Debug)]
+pub enum PlayError {
+    Story(StoryError),
+    Localization(LocalizationError),
+    UnknownLanguage(String),
+    Graph(GraphError),
+    Io(io::Error),
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::Story(error) => write!(f, "{error}"),
+            PlayError::Localization(error) => write!(f, "{error}"),
+            PlayError::UnknownLanguage(code) => {
+                let codes: Vec<&str> = Language::ALL
+                    .iter()
+                    .map(|language| language.code())
+                    .collect();
+                write!(
+                    f,
+                    "there's no `{code}` language, try one of {}",
+                    codes.join(", ")
+                )
+            }
+            PlayError::Graph(error) => write!(f, "the story got lost: {error}"),
+            PlayError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+impl From<GraphError> for PlayError {
+    fn from(error: GraphError) -> Self {
+        PlayError::Graph(error)
+    }
+}
+
+impl From<io::Error> for PlayError {
+    fn from(error: io::Error) -> Self {
+        PlayError::Io(error)
+    }
+}
+
+/// How a game in the terminal finished.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The book got to the ending at that node.
+    Ended(usize),
+    /// The reader quit, or there was nothing more to read from the input.
+    Quit,
+}
+
+enum Command {
+    Go(Option<usize>),
+    Back,
+    Quit,
+}
+
+pub struct Player {
+    graph: BookGraph,
+    locales: Locales,
+    language: Language,
+}
+
+impl Player {
+    pub fn new(
+        story: &StoryFile,
+        translations: impl IntoIterator<Item = (Language, FluentResource)>,
+        language: Language,
+    ) -> Result<Self, PlayError> {
+        let story_texts = localization::story_ftl(story).map_err(PlayError::Localization)?;
+        let translations = translations
+            .into_iter()
+            .map(|(language, resource)| (language, Arc::new(resource)));
+        Ok(Self {
+            graph: story.to_graph(),
+            locales: Locales::new(Arc::new(story_texts), translations),
+            language,
+        })
+    }
+
+    /// The story at `path`, with every `.ftl` file of the folder of each language in `locales`.
+    /// In the language with the given code, like `ca`, or in the one the story is written in.
+    pub fn load(
+        path: impl AsRef<Path>,
+        locales: impl AsRef<Path>,
+        language: Option<&str>,
+    ) -> Result<Self, PlayError> {
+        let language = match language {
+            Some(code) => Language::from_code(code)
+                .ok_or_else(|| PlayError::UnknownLanguage(code.to_string()))?,
+            None => Language::DEFAULT,
+        };
+        let text =
+            fs::read_to_string(path).map_err(|error| PlayError::Story(StoryError::Io(error)))?;
+        let story = StoryFile::from_ron(&text).map_err(PlayError::Story)?;
+        let translations =
+            localization::read_translations(locales.as_ref()).map_err(PlayError::Localization)?;
+        Self::new(&story, translations, language)
+    }
+
+    /// Plays from the current page until the end of the book or until the reader quits.
+    pub fn play(
+        &mut self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+    ) -> Result<Outcome, PlayError> {
+        loop {
+            let index = self.graph.get_current_index();
+            // A fork without any choice to take fails, instead of asking for one forever.
+            let node = self.graph.try_get_playable_node()?.clone();
+            writeln!(output, "\n[{index}]")?;
+            match node {
+                Node::Simple {
+                    content,
+                    extra,
+                    next,
+                } => {
+                    self.write_text(
+                        &mut output,
+                        &localization::node_key(index, "text"),
+                        &content.text,
+                    )?;
+                    self.write_text(
+                        &mut output,
+                        &localization::node_key(index, "additional-text"),
+                        &extra.additional_text,
+                    )?;
+                    if next.is_none() {
+                        writeln!(output, "\nTHE END")?;
+                        return Ok(Outcome::Ended(index));
+                    }
+                    write!(output, "\n(Enter) go on, (b) back, (q) quit: ")?;
+                    output.flush()?;
+                    match read_command(&mut input)? {
+                        Command::Go(_) => self.graph.try_advance()?,
+                        Command::Back => self.go_back(&mut output)?,
+                        Command::Quit => return Ok(Outcome::Quit),
+                    }
+                }
+                Node::Fork { content, choices } => {
+                    self.write_text(
+                        &mut output,
+                        &localization::node_key(index, "text"),
+                        &content.text,
+                    )?;
+                    // Numbered as they are shown, hidden choices aren't.
+                    let shown: Vec<_> = choices
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, choice)| choice.is_shown(&self.graph.context))
+                        .collect();
+                    writeln!(output)?;
+                    for (number, (position, choice)) in shown.iter().enumerate() {
+                        let text = self.text(
+                            &localization::choice_key(index, *position, "text"),
+                            &choice.text,
+                        );
+                        match choice.locked_reason(&self.graph.context) {
+                            Some(reason) => {
+                                let reason = self.text(
+                                    &localization::choice_key(index, *position, "locked-reason"),
+                                    reason,
+                                );
+                                writeln!(output, "  {}. {text} (locked: {reason})", number + 1)?;
+                            }
+                            None => writeln!(output, "  {}. {text}", number + 1)?,
+                        }
+                    }
+                    loop {
+                        write!(output, "\n(1-{}) choose, (b) back, (q) quit: ", shown.len())?;
+                        output.flush()?;
+                        let number = match read_command(&mut input)? {
+                            Command::Go(number) => number,
+                            Command::Back => {
+                                self.go_back(&mut output)?;
+                                break;
+                            }
+                            Command::Quit => return Ok(Outcome::Quit),
+                        };
+                        let Some((position, choice)) =
+                            number.and_then(|number| shown.get(number.checked_sub(1)?))
+                        else {
+                            writeln!(output, "That's not one of the choices.")?;
+                            continue;
+                        };
+                        if choice.locked_reason(&self.graph.context).is_some() {
+                            writeln!(output, "That choice is locked.")?;
+                            continue;
+                        }
+                        let key = localization::choice_key(index, *position, "additional-text");
+                        let additional_text = self.text(&key, &choice.additional_text);
+                        self.graph.try_choose(*position)?;
+                        if !additional_text.is_empty() {
+                            writeln!(output, "\n{additional_text}")?;
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The text in the language of the player, without markup.
+    fn text(&self, key: &str, text: &Text) -> String {
+        let text = self
+            .locales
+            .text(self.language, key, text, &self.graph.context);
+        markup::plain_text(&text)
+    }
+
+    fn write_text(&self, output: &mut impl Write, key: &str, text: &Text) -> io::Result<()> {
+        let text = self.text(key, text);
+        if text.is_empty() {
+            return Ok(());
+        }
+        writeln!(output, "{text}")
+    }
+
+    fn go_back(&mut self, output: &mut impl Write) -> io::Result<()> {
+        if !self.graph.go_back() {
+            writeln!(output, "This is the first page.")?;
+        }
+        Ok(())
+    }
+}
+
+/// The end of the input is like quitting.
+fn read_command(input: &mut impl BufRead) -> io::Result<Command> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(Command::Quit);
+    }
+    Ok(match line.trim() {
+        "b" => Command::Back,
+        "q" => Command::Quit,
+        other => Command::Go(other.parse().ok()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(input: &str) -> (Outcome, String) {
+        let story = StoryFile::from_ron(include_str!("../assets/story/book.story.ron")).unwrap();
+        let catalan =
+            localization::parse_ftl(include_str!("../assets/locales/ca/book.ftl")).unwrap();
+        let mut player =
+            Player::new(&story, [(Language::Catalan, catalan)], Language::Catalan).unwrap();
+        let mut output = Vec::new();
+        let outcome = player.play(input.as_bytes(), &mut output).unwrap();
+        (outcome, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn the_book_can_be_played_from_a_script() {
+        let (outcome, output) = play("q\n");
+        assert_eq!(outcome, Outcome::Quit);
+        assert!(output.starts_with("\n[0]\n"));
+        assert!(!output.contains('*'));
+
+        // Always the first choice, and going on otherwise.
+        let (outcome, output) = play(&"1\n".repeat(100));
+        assert!(matches!(outcome, Outcome::Ended(_)), "{output}");
+        assert!(output.ends_with("THE END\n"));
+    }
+
+    #[test]
+    fn forks_without_a_choice_to_take_fail() {
+        let story = StoryFile::from_ron(
+            r#"(nodes: [
+                (0, Fork(content: (text: "Una puerta"), choices: [
+                    (text: "Abrir", next: 1, availability: Locked(condition: "false", reason: "Cerrada")),
+                    (text: "Forzar", next: 1, availability: Hidden("false")),
+                ])),
+                (1, Simple(content: (text: "Fin"), next: None)),
+            ])"#,
+        )
+        .unwrap();
+        let mut player = Player::new(&story, [], Language::Spanish).unwrap();
+        let result = player.play("1\n".repeat(3).as_bytes(), Vec::new());
+        assert!(matches!(
+            result,
+            Err(PlayError::Graph(GraphError::DeadEnd(0)))
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! Writes the text of the page character by character, as if it was being written right then.
+//! The characters still to be written are there but transparent, so the text doesn't move around
+//! while it's written and the pages are measured with the whole text.
+
+#![allow(clippy::too_many_arguments)]
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+use crate::{
+    actions::{Action, Actions},
+    loading::AudioAssets,
+};
+
+/// How fast texts are written.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct TextSpeed {
+    /// The text is shown all at once if it isn't positive.
+    pub characters_per_second: f32,
+}
+
+impl Default for TextSpeed {
+    fn default() -> Self {
+        Self {
+            characters_per_second: 40.,
+        }
+    }
+}
+
+/// A text written character by character once it's shown.
+/// It's written again every time it's hidden and shown, like when the page is turned.
+#[derive(Component, Default)]
+pub struct Typewriter {
+    /// The whole text, while it's being written.
+    sections: Option<Vec<TextSection>>,
+    written: f32,
+    finished: bool,
+}
+
+impl Typewriter {
+    pub fn is_writing(&self) -> bool {
+        self.sections.is_some()
+    }
+}
+
+/// [`Action::Skip`] writes the whole text at once.
+pub fn write_texts(
+    mut texts: Query<(&mut Text, &mut Typewriter, &Visibility)>,
+    speed: Res<TextSpeed>,
+    time: Res<Time>,
+    actions: Res<Actions>,
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut scribble: Local<Option<Handle<AudioInstance>>>,
+) {
+    let skip = actions.just_pressed(Action::Skip);
+    for (mut text, mut typewriter, visibility) in texts.iter_mut() {
+        let typewriter = &mut *typewriter;
+        if let Visibility::Hidden = visibility {
+            *typewriter = Typewriter::default();
+            continue;
+        }
+        if typewriter.finished {
+            continue;
+        }
+        let sections = typewriter
+            .sections
+            .get_or_insert_with(|| text.sections.clone());
+        let length: usize = sections
+            .iter()
+            .map(|section| section.value.chars().count())
+            .sum();
+        typewriter.written += speed.characters_per_second * time.delta_seconds();
+        let written = typewriter.written as usize;
+        if skip || speed.characters_per_second <= 0. || written >= length {
+            text.sections = typewriter.sections.take().unwrap_or_default();
+            typewriter.finished = true;
+        } else {
+            text.sections = reveal(sections, written);
+        }
+    }
+
+    let writing = texts
+        .iter()
+        .any(|(_, typewriter, _)| typewriter.is_writing());
+    if writing && scribble.is_none() {
+        *scribble = Some(audio.play(audio_assets.scribble.clone()).looped().handle());
+    } else if !writing {
+        if let Some(instance) = scribble
+            .take()
+            .and_then(|handle| audio_instances.get_mut(&handle))
+        {
+            instance.stop(AudioTween::default());
+        }
+    }
+}
+
+/// The sections with only the first `written` characters showing, in their own style.
+fn reveal(sections: &[TextSection], written: usize) -> Vec<TextSection> {
+    let mut left = written;
+    let mut revealed = Vec::new();
+    for section in sections.iter() {
+        let shown = section
+            .value
+            .char_indices()
+            .nth(left)
+            .map_or(section.value.len(), |(index, _)| index);
+        left -= section.value[..shown].chars().count();
+        let (before, after) = section.value.split_at(shown);
+        if !before.is_empty() {
+            revealed.push(TextSection::new(before, section.style.clone()));
+        }
+        if !after.is_empty() {
+            let mut style = section.style.clone();
+            style.color = style.color.with_a(0.);
+            revealed.push(TextSection::new(after, style));
+        }
+    }
+    revealed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texts_are_revealed_in_their_own_style() {
+        let style = |font_size| TextStyle {
+            font_size,
+            ..default()
+        };
+        let sections = vec![
+            TextSection::new("É", style(100.)),
+            TextSection::new("rase una ", style(30.)),
+            TextSection::new("bestia", style(31.)),
+        ];
+        let revealed = reveal(&sections, 12);
+        let shown: Vec<_> = revealed
+            .iter()
+            .map(|section| {
+                (
+                    section.value.as_str(),
+                    section.style.font_size,
+                    section.style.color.a(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            shown,
+            vec![
+                ("É", 100., 1.),
+                ("rase una ", 30., 1.),
+                ("be", 31., 1.),
+                ("stia", 31., 0.),
+            ]
+        );
+        assert_eq!(reveal(&sections, 0).len(), 3);
+        assert_eq!(reveal(&sections, 16).len(), 3);
+    }
+}
@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::expression::{Value, Variables};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Variable {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+    /// Counts up from zero, like how many times a page was visited.
+    Counter(u32),
+}
+
+impl Variable {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Variable::Bool(_) => "a boolean",
+            Variable::Int(_) => "an integer",
+            Variable::Text(_) => "a text",
+            Variable::Counter(_) => "a counter",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableError {
+    Unknown(String),
+    WrongType {
+        name: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for VariableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableError::Unknown(name) => write!(f, "there's no variable `{name}`"),
+            VariableError::WrongType {
+                name,
+                expected,
+                found,
+            } => write!(f, "`{name}` is {found} but {expected} was expected"),
+        }
+    }
+}
+
+impl std::error::Error for VariableError {}
+
+/// Variables keyed by name, the state of a story.
+/// Only declared variables can be set, and they keep the type they were declared with,
+/// so a typo in a story file is an error instead of a new variable.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VariableStore {
+    variables: BTreeMap<String, Variable>,
+}
+
+impl VariableStore {
+    /// The store with the variables declared, along with their default values.
+    pub fn new(defaults: BTreeMap<String, Variable>) -> Self {
+        Self {
+            variables: defaults,
+        }
+    }
+
+    pub fn declare(&mut self, name: &str, default: Variable) {
+        self.variables.insert(name.to_string(), default);
+    }
+
+    /// Declares the variables this store is missing, like the ones added to a story
+    /// after its progress was saved.
+    pub fn declare_missing(&mut self, defaults: &VariableStore) {
+        for (name, default) in defaults.variables.iter() {
+            self.variables
+                .entry(name.clone())
+                .or_insert_with(|| default.clone());
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Variable> {
+        self.variables.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Variable)> {
+        self.variables
+            .iter()
+            .map(|(name, variable)| (name.as_str(), variable))
+    }
+
+    fn get_typed<'a, T>(
+        &'a self,
+        name: &str,
+        expected: &'static str,
+        typed: impl Fn(&'a Variable) -> Option<T>,
+    ) -> Result<T, VariableError> {
+        let variable = self
+            .variables
+            .get(name)
+            .ok_or_else(|| VariableError::Unknown(name.to_string()))?;
+        typed(variable).ok_or_else(|| VariableError::WrongType {
+            name: name.to_string(),
+            expected,
+            found: variable.kind(),
+        })
+    }
+
+    pub fn get_bool(&self, name: &str) -> Result<bool, VariableError> {
+        self.get_typed(name, "a boolean", |variable| match variable {
+            Variable::Bool(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn get_int(&self, name: &str) -> Result<i64, VariableError> {
+        self.get_typed(name, "an integer", |variable| match variable {
+            Variable::Int(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn get_text(&self, name: &str) -> Result<&str, VariableError> {
+        self.get_typed(name, "a text", |variable| match variable {
+            Variable::Text(value) => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn get_counter(&self, name: &str) -> Result<u32, VariableError> {
+        self.get_typed(name, "a counter", |variable| match variable {
+            Variable::Counter(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Replaces the value, which must have the type the variable was declared with.
+    pub fn set(&mut self, name: &str, value: Variable) -> Result<(), VariableError> {
+        let variable = self
+            .variables
+            .get_mut(name)
+            .ok_or_else(|| VariableError::Unknown(name.to_string()))?;
+        if variable.kind() != value.kind() {
+            return Err(VariableError::WrongType {
+                name: name.to_string(),
+                expected: variable.kind(),
+                found: value.kind(),
+            });
+        }
+        *variable = value;
+        Ok(())
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) -> Result<(), VariableError> {
+        self.set(name, Variable::Bool(value))
+    }
+
+    pub fn set_int(&mut self, name: &str, value: i64) -> Result<(), VariableError> {
+        self.set(name, Variable::Int(value))
+    }
+
+    pub fn set_text(&mut self, name: &str, value: &str) -> Result<(), VariableError> {
+        self.set(name, Variable::Text(value.to_string()))
+    }
+
+    pub fn set_counter(&mut self, name: &str, value: u32) -> Result<(), VariableError> {
+        self.set(name, Variable::Counter(value))
+    }
+
+    /// Returns the new count.
+    pub fn increment(&mut self, name: &str) -> Result<u32, VariableError> {
+        let count = self.get_counter(name)?.saturating_add(1);
+        self.set_counter(name, count)?;
+        Ok(count)
+    }
+}
+
+/// Counters are integers for expressions, but can't be set below zero.
+impl Variables for VariableStore {
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        Some(match self.variables.get(name)? {
+            Variable::Bool(value) => Value::Bool(*value),
+            Variable::Int(value) => Value::Int(*value),
+            Variable::Text(value) => Value::Text(value.clone()),
+            Variable::Counter(value) => Value::Int(i64::from(*value)),
+        })
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> bool {
+        let variable = match (self.variables.get(name), value) {
+            (Some(Variable::Bool(_)), Value::Bool(value)) => Variable::Bool(value),
+            (Some(Variable::Int(_)), Value::Int(value)) => Variable::Int(value),
+            (Some(Variable::Text(_)), Value::Text(value)) => Variable::Text(value),
+            (Some(Variable::Counter(_)), Value::Int(value)) => match u32::try_from(value) {
+                Ok(value) => Variable::Counter(value),
+                Err(_) => return false,
+            },
+            _ => return false,
+        };
+        self.set(name, variable).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expression::Script;
+
+    fn store() -> VariableStore {
+        VariableStore::new(BTreeMap::from([
+            ("fan_dragones".to_string(), Variable::Bool(false)),
+            ("afecto".to_string(), Variable::Int(0)),
+            (
+                "nombre".to_string(),
+                Variable::Text("Cleodolinda".to_string()),
+            ),
+            ("visitas".to_string(), Variable::Counter(0)),
+        ]))
+    }
+
+    #[test]
+    fn typed_getters_and_setters_work() {
+        let mut store = store();
+        store.set_bool("fan_dragones", true).unwrap();
+        store.set_int("afecto", -3).unwrap();
+        store.set_text("nombre", "Cleo").unwrap();
+        assert_eq!(store.increment("visitas"), Ok(1));
+        assert_eq!(store.get_bool("fan_dragones"), Ok(true));
+        assert_eq!(store.get_int("afecto"), Ok(-3));
+        assert_eq!(store.get_text("nombre"), Ok("Cleo"));
+        assert_eq!(store.get_counter("visitas"), Ok(1));
+
+        assert_eq!(
+            store.set_int("fan_dragones", 1),
+            Err(VariableError::WrongType {
+                name: "fan_dragones".to_string(),
+                expected: "a boolean",
+                found: "an integer",
+            })
+        );
+        assert_eq!(
+            store.get_bool("volar"),
+            Err(VariableError::Unknown("volar".to_string()))
+        );
+    }
+
+    #[test]
+    fn scripts_use_the_store() {
+        let mut store = store();
+        Script::parse("visitas += 2; afecto = visitas - 5; nombre = 'Cleo'")
+            .unwrap()
+            .run(&mut store)
+            .unwrap();
+        assert_eq!(store.get_counter("visitas"), Ok(2));
+        assert_eq!(store.get_int("afecto"), Ok(-3));
+        assert_eq!(store.get_text("nombre"), Ok("Cleo"));
+        // Counters can't go below zero.
+        assert!(Script::parse("visitas -= 3")
+            .unwrap()
+            .run(&mut store)
+            .is_err());
+        assert_eq!(store.get_counter("visitas"), Ok(2));
+    }
+
+    #[test]
+    fn missing_variables_are_declared() {
+        let mut saved = VariableStore::default();
+        saved.declare("fan_dragones", Variable::Bool(true));
+        saved.declare_missing(&store());
+        assert_eq!(saved.get_bool("fan_dragones"), Ok(true));
+        assert_eq!(saved.get_counter("visitas"), Ok(0));
+    }
+}